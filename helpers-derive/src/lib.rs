@@ -0,0 +1,251 @@
+//! Derive macros for the `helpers` crate
+//!
+//! Use them through `helpers` with the `derive` feature rather than depending
+//! on this crate directly:
+//! ```toml
+//! [dependencies]
+//! helpers = { version = "x.x.x", features = ["derive"] }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+use syn::{
+  parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Expr, Fields, Path, Result,
+};
+
+/// Implements `helpers::rand::Random` for a struct or enum
+///
+/// Every field is generated with its own `Random` implementation unless one
+/// of these field attributes is given:
+///
+/// - `#[random(range = 1..=100)]`: a number within the range
+/// - `#[random(len = 3..8)]` or `#[random(len = 4)]`: a `String` or `Vec` of that length
+/// - `#[random(choose = ["a", "b"])]`: one of the listed values, converted with `Into`
+/// - `#[random(with = path::to::function)]`: calls `fn(&mut Rng) -> T`
+/// - `#[random(default)]`: `Default::default()`
+///
+/// Enum variants are equally likely unless weighted with
+/// `#[random(weight = 3)]`; `#[random(skip)]` never picks a variant.
+///
+/// Recursive types terminate: after `helpers::rand::random::MAX_DEPTH` levels
+/// of nesting, collections are empty, options are `None` and enums only pick
+/// variants whose fields do not name the enum itself.
+#[proc_macro_derive(Random, attributes(random))]
+pub fn derive_random(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand(input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2> {
+  for param in input.generics.type_params_mut() {
+    param.bounds.push(parse_quote!(::helpers::rand::Random));
+  }
+  let name = &input.ident;
+  let body = match &input.data {
+    Data::Struct(data) => construct(quote!(Self), &data.fields)?,
+    Data::Enum(data) => {
+      let mut arms = Vec::new();
+      let mut weights = Vec::new();
+      let mut recursive = Vec::new();
+      for variant in &data.variants {
+        let options = VariantOptions::parse(&variant.attrs)?;
+        if options.skip {
+          continue;
+        }
+        let ident = &variant.ident;
+        let index = arms.len();
+        let value = construct(quote!(Self::#ident), &variant.fields)?;
+        arms.push(quote!(#index => #value));
+        weights.push(match options.weight {
+          Some(weight) => quote!((#weight) as f64),
+          None => quote!(1.0),
+        });
+        recursive.push(
+          variant
+            .fields
+            .iter()
+            .any(|field| mentions(field.ty.to_token_stream(), name)),
+        );
+      }
+      if arms.is_empty() {
+        return Err(Error::new(
+          name.span(),
+          "`Random` needs at least one variant that is not skipped",
+        ));
+      }
+      let indices = 0..arms.len();
+      // Only worth checking the depth when some variants end the recursion.
+      let weights = if recursive.contains(&true) && recursive.contains(&false) {
+        quote! {
+          let mut weights = [#(#weights),*];
+          if ::helpers::rand::random::too_deep() {
+            for (weight, recursive) in weights.iter_mut().zip([#(#recursive),*]) {
+              if recursive {
+                *weight = 0.0;
+              }
+            }
+          }
+        }
+      } else {
+        quote!(let weights = [#(#weights),*];)
+      };
+      quote! {
+        let indices = [#(#indices),*];
+        #weights
+        match *rng
+          .weighted_choice(&indices, &weights)
+          .expect("variant weights must be finite, non-negative and not all zero")
+        {
+          #(#arms,)*
+          _ => unreachable!(),
+        }
+      }
+    }
+    Data::Union(_) => {
+      return Err(Error::new(
+        name.span(),
+        "`Random` cannot be derived for unions",
+      ))
+    }
+  };
+  let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+  Ok(quote! {
+    impl #impl_generics ::helpers::rand::Random for #name #type_generics #where_clause {
+      fn random_with(rng: &mut ::helpers::rand::Rng) -> Self {
+        ::helpers::rand::random::nested(|| { #body })
+      }
+    }
+  })
+}
+
+/// Whether `tokens`, such as a field type, name the type `name` or `Self`
+fn mentions(tokens: TokenStream2, name: &Ident) -> bool {
+  tokens.into_iter().any(|token| match token {
+    TokenTree::Ident(ident) => ident == *name || ident == "Self",
+    TokenTree::Group(group) => mentions(group.stream(), name),
+    _ => false,
+  })
+}
+
+/// Builds `path { .. }`, `path(..)` or `path` with every field generated
+fn construct(path: TokenStream2, fields: &Fields) -> Result<TokenStream2> {
+  let values = fields
+    .iter()
+    .enumerate()
+    .map(|(i, field)| {
+      let label = field
+        .ident
+        .as_ref()
+        .map_or_else(|| i.to_string(), ToString::to_string);
+      let value = FieldOptions::parse(&field.attrs)?.generate(&field.ty, &label);
+      Ok(match &field.ident {
+        Some(ident) => quote!(#ident: #value),
+        None => value,
+      })
+    })
+    .collect::<Result<Vec<_>>>()?;
+  Ok(match fields {
+    Fields::Named(_) => quote!(#path { #(#values),* }),
+    Fields::Unnamed(_) => quote!(#path(#(#values),*)),
+    Fields::Unit => path,
+  })
+}
+
+enum FieldOptions {
+  Random,
+  Range(Expr),
+  Len(Expr),
+  Choose(Expr),
+  With(Path),
+  Default,
+}
+
+impl FieldOptions {
+  fn parse(attrs: &[Attribute]) -> Result<Self> {
+    let mut options = FieldOptions::Random;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("random")) {
+      attr.parse_nested_meta(|meta| {
+        if !matches!(options, FieldOptions::Random) {
+          return Err(meta.error("only one `random` option is allowed per field"));
+        }
+        options = if meta.path.is_ident("range") {
+          FieldOptions::Range(meta.value()?.parse()?)
+        } else if meta.path.is_ident("len") {
+          FieldOptions::Len(meta.value()?.parse()?)
+        } else if meta.path.is_ident("choose") {
+          FieldOptions::Choose(meta.value()?.parse()?)
+        } else if meta.path.is_ident("with") {
+          FieldOptions::With(meta.value()?.parse()?)
+        } else if meta.path.is_ident("default") {
+          FieldOptions::Default
+        } else {
+          return Err(meta.error("expected `range`, `len`, `choose`, `with` or `default`"));
+        };
+        Ok(())
+      })?;
+    }
+    Ok(options)
+  }
+
+  fn generate(&self, ty: &syn::Type, label: &str) -> TokenStream2 {
+    match self {
+      FieldOptions::Random => {
+        quote_spanned!(ty.span()=> <#ty as ::helpers::rand::Random>::random_with(rng))
+      }
+      FieldOptions::Range(range) => quote_spanned! {range.span()=>
+        rng
+          .range(#range)
+          .unwrap_or_else(|e| panic!("invalid range for `{}`: {}", #label, e))
+      },
+      FieldOptions::Len(Expr::Range(range)) => quote_spanned! {range.span()=>
+        {
+          let len = rng
+            .range(#range)
+            .unwrap_or_else(|e| panic!("invalid length for `{}`: {}", #label, e));
+          <#ty as ::helpers::rand::RandomLen>::random_len_with(rng, len)
+        }
+      },
+      FieldOptions::Len(len) => quote_spanned! {len.span()=>
+        <#ty as ::helpers::rand::RandomLen>::random_len_with(rng, #len)
+      },
+      FieldOptions::Choose(items) => quote_spanned! {items.span()=>
+        ::core::convert::Into::into(::core::clone::Clone::clone(
+          rng
+            .random_choice(&#items)
+            .unwrap_or_else(|| panic!("nothing to choose from for `{}`", #label)),
+        ))
+      },
+      FieldOptions::With(path) => quote_spanned!(path.span()=> #path(rng)),
+      FieldOptions::Default => quote!(::core::default::Default::default()),
+    }
+  }
+}
+
+#[derive(Default)]
+struct VariantOptions {
+  weight: Option<Expr>,
+  skip: bool,
+}
+
+impl VariantOptions {
+  fn parse(attrs: &[Attribute]) -> Result<Self> {
+    let mut options = VariantOptions::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("random")) {
+      attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("weight") {
+          options.weight = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("skip") {
+          options.skip = true;
+        } else {
+          return Err(meta.error("expected `weight` or `skip`"));
+        }
+        Ok(())
+      })?;
+    }
+    Ok(options)
+  }
+}
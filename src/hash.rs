@@ -1,11 +1,21 @@
 //! Provides a variety of common hash functions
 
+use base64::Engine;
+use hmac::Mac;
 use md5::Digest;
+use std::io::{self, Read};
 
 pub use argon2::Error;
 pub use bcrypt::BcryptError;
 pub use bcrypt::Version;
 
+pub mod cdc;
+pub mod filter;
+pub mod manifest;
+pub mod password;
+pub mod ring;
+pub mod similarity;
+
 /// Computes the MD5 hash of the input byte slice
 ///
 /// # Parameters
@@ -32,11 +42,127 @@ pub fn md5(data: &str) -> String {
   base16ct::lower::encode_string(&hasher.finalize())
 }
 
-pub fn sha1() {}
+/// Computes the SHA-1 hash of the input byte slice
+///
+/// # Parameters
+///
+/// * `data` - A reference to the byte slice to compute the SHA-1 hash for
+///
+/// # Returns
+///
+/// The lowercase hexadecimal string of the 160-bit digest
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::sha1;
+///
+/// let hash = sha1(b"hello world");
+/// assert_eq!(hash, "2aae6c35c94fcfb415dbe95f408b9ce91ee846ed");
+/// ```
+///
+/// # Notes
+///
+/// SHA-1 is broken for collision resistance, only use it for interoperability
+pub fn sha1(data: &[u8]) -> String {
+  let mut hasher = sha1::Sha1::new();
+  hasher.update(data);
+  base16ct::lower::encode_string(&hasher.finalize())
+}
 
-pub fn sha256() {}
+/// Computes the SHA-256 hash of the input byte slice
+///
+/// # Parameters
+///
+/// * `data` - A reference to the byte slice to compute the SHA-256 hash for
+///
+/// # Returns
+///
+/// The lowercase hexadecimal string of the 256-bit digest
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::sha256;
+///
+/// let hash = sha256(b"abc");
+/// assert_eq!(hash, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+/// ```
+pub fn sha256(data: &[u8]) -> String {
+  let mut hasher = sha2::Sha256::new();
+  hasher.update(data);
+  base16ct::lower::encode_string(&hasher.finalize())
+}
 
-pub fn sha512() {}
+/// Computes the SHA-512 hash of the input byte slice
+///
+/// # Parameters
+///
+/// * `data` - A reference to the byte slice to compute the SHA-512 hash for
+///
+/// # Returns
+///
+/// The lowercase hexadecimal string of the 512-bit digest
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::sha512;
+///
+/// let hash = sha512(b"hello world");
+/// assert_eq!(hash.len(), 128);
+/// ```
+pub fn sha512(data: &[u8]) -> String {
+  let mut hasher = sha2::Sha512::new();
+  hasher.update(data);
+  base16ct::lower::encode_string(&hasher.finalize())
+}
+
+/// Computes the SHA-256 hash of everything read from `reader`
+///
+/// The input is processed in fixed-size blocks, so large files are never
+/// loaded into memory at once.
+///
+/// # Returns
+///
+/// The same digest as [`sha256`] over the whole stream, or the I/O error that stopped it
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::{sha256, sha256_reader};
+///
+/// let hash = sha256_reader(&b"abc"[..]).unwrap();
+/// assert_eq!(hash, sha256(b"abc"));
+/// ```
+pub fn sha256_reader<R: Read>(reader: R) -> io::Result<String> {
+  let mut hasher = sha2::Sha256::new();
+  digest_reader(reader, |block| hasher.update(block))?;
+  Ok(base16ct::lower::encode_string(&hasher.finalize()))
+}
+
+/// Computes the SHA-512 hash of everything read from `reader`
+///
+/// # Returns
+///
+/// The same digest as [`sha512`] over the whole stream, or the I/O error that stopped it
+pub fn sha512_reader<R: Read>(reader: R) -> io::Result<String> {
+  let mut hasher = sha2::Sha512::new();
+  digest_reader(reader, |block| hasher.update(block))?;
+  Ok(base16ct::lower::encode_string(&hasher.finalize()))
+}
+
+fn digest_reader<R: Read>(mut reader: R, mut update: impl FnMut(&[u8])) -> io::Result<()> {
+  let mut buf = [0u8; 64 * 1024];
+  loop {
+    match reader.read(&mut buf) {
+      Ok(0) => return Ok(()),
+      Ok(n) => update(&buf[..n]),
+      Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+      Err(e) => return Err(e),
+    }
+  }
+}
 
 /// Compute the BLAKE3 hash of input data
 ///
@@ -74,6 +200,131 @@ pub fn blake3(data: &[u8]) -> String {
   hased.to_string()
 }
 
+/// Computes the BLAKE3 hash of everything read from `reader`
+///
+/// # Returns
+///
+/// The same digest as [`blake3`] over the whole stream, or the I/O error that stopped it
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::{blake3, blake3_reader};
+///
+/// let file = std::io::Cursor::new(b"hello world");
+/// assert_eq!(blake3_reader(file).unwrap(), blake3(b"hello world"));
+/// ```
+pub fn blake3_reader<R: Read>(reader: R) -> io::Result<String> {
+  let mut hasher = blake3::Hasher::new();
+  digest_reader(reader, |block| {
+    hasher.update(block);
+  })?;
+  Ok(hasher.finalize().to_string())
+}
+
+const XXH_PRIME64_1: u64 = 0x9e37_79b1_85eb_ca87;
+const XXH_PRIME64_2: u64 = 0xc2b2_ae3d_27d4_eb4f;
+const XXH_PRIME64_3: u64 = 0x1656_67b1_9e37_79f9;
+const XXH_PRIME64_4: u64 = 0x85eb_ca77_c2b2_ae63;
+const XXH_PRIME64_5: u64 = 0x27d4_eb2f_1656_67c5;
+
+/// Computes the 64-bit xxHash (XXH64) of the input byte slice
+///
+/// A fast non-cryptographic hash, meant for hash tables, sharding and
+/// probabilistic data structures rather than integrity or security.
+///
+/// # Parameters
+///
+/// * `data` - A reference to the byte slice to hash
+/// * `seed` - Seed value, different seeds give independent hash functions
+///
+/// # Returns
+///
+/// The 64-bit hash value, identical to the reference XXH64 implementation
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::xxh64;
+///
+/// assert_eq!(xxh64(b"abc", 0), 0x44bc2cf5ad770999);
+/// ```
+pub fn xxh64(data: &[u8], seed: u64) -> u64 {
+  fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().expect("8 bytes"))
+  }
+  fn read_u32(bytes: &[u8]) -> u64 {
+    u32::from_le_bytes(bytes[..4].try_into().expect("4 bytes")) as u64
+  }
+  fn round(acc: u64, input: u64) -> u64 {
+    acc
+      .wrapping_add(input.wrapping_mul(XXH_PRIME64_2))
+      .rotate_left(31)
+      .wrapping_mul(XXH_PRIME64_1)
+  }
+  fn merge(acc: u64, val: u64) -> u64 {
+    (acc ^ round(0, val))
+      .wrapping_mul(XXH_PRIME64_1)
+      .wrapping_add(XXH_PRIME64_4)
+  }
+
+  let len = data.len();
+  let mut rest = data;
+  let mut hash = if len >= 32 {
+    let mut v = [
+      seed.wrapping_add(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_2),
+      seed.wrapping_add(XXH_PRIME64_2),
+      seed,
+      seed.wrapping_sub(XXH_PRIME64_1),
+    ];
+    while rest.len() >= 32 {
+      for (i, acc) in v.iter_mut().enumerate() {
+        *acc = round(*acc, read_u64(&rest[i * 8..]));
+      }
+      rest = &rest[32..];
+    }
+    let mut hash = v[0]
+      .rotate_left(1)
+      .wrapping_add(v[1].rotate_left(7))
+      .wrapping_add(v[2].rotate_left(12))
+      .wrapping_add(v[3].rotate_left(18));
+    for acc in v {
+      hash = merge(hash, acc);
+    }
+    hash
+  } else {
+    seed.wrapping_add(XXH_PRIME64_5)
+  };
+  hash = hash.wrapping_add(len as u64);
+
+  while rest.len() >= 8 {
+    hash ^= round(0, read_u64(rest));
+    hash = hash
+      .rotate_left(27)
+      .wrapping_mul(XXH_PRIME64_1)
+      .wrapping_add(XXH_PRIME64_4);
+    rest = &rest[8..];
+  }
+  if rest.len() >= 4 {
+    hash ^= read_u32(rest).wrapping_mul(XXH_PRIME64_1);
+    hash = hash
+      .rotate_left(23)
+      .wrapping_mul(XXH_PRIME64_2)
+      .wrapping_add(XXH_PRIME64_3);
+    rest = &rest[4..];
+  }
+  for &byte in rest {
+    hash ^= (byte as u64).wrapping_mul(XXH_PRIME64_5);
+    hash = hash.rotate_left(11).wrapping_mul(XXH_PRIME64_1);
+  }
+
+  hash ^= hash >> 33;
+  hash = hash.wrapping_mul(XXH_PRIME64_2);
+  hash ^= hash >> 29;
+  hash = hash.wrapping_mul(XXH_PRIME64_3);
+  hash ^ (hash >> 32)
+}
+
 pub fn escrypt() {}
 
 /// Generates a bcrypt hash for the given password
@@ -136,6 +387,102 @@ pub fn bcrypt_custom(
   Ok(bcrypt::hash_with_result(password, cost)?.format_for_version(version))
 }
 
+/// Prefix of the hashes produced by [`BcryptMode::Prehash`]
+///
+/// Deliberately different from passlib's `$bcrypt-sha256$`, whose format is
+/// not compatible with these hashes.
+pub const BCRYPT_PREHASH_PREFIX: &str = "$helpers-bcrypt-hmac$";
+
+/// Prefix of the hashes produced by [`BcryptMode::Strict`]
+pub const BCRYPT_STRICT_PREFIX: &str = "$helpers-bcrypt-strict$";
+
+/// HMAC key used to prehash passwords, acts as domain separation
+const BCRYPT_PREHASH_KEY: &[u8] = b"helpers.hash.bcrypt-sha256.v1";
+
+/// Maximum number of password bytes used by bcrypt
+pub const BCRYPT_MAX_PASSWORD_LEN: usize = 72;
+
+/// How passwords longer than 72 bytes are handled by bcrypt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BcryptMode {
+  /// Silently ignore bytes after the 72nd, the behavior of [`bcrypt`]
+  #[default]
+  Truncate,
+  /// Fail with `BcryptError::Truncation` when the password is too long
+  ///
+  /// The resulting hash starts with [`BCRYPT_STRICT_PREFIX`], which lets
+  /// [`verify_bcrypt`] reject long passwords instead of truncating them.
+  Strict,
+  /// Hash the password with HMAC-SHA256 and base64 before bcrypt, so every byte counts
+  ///
+  /// The resulting hash starts with [`BCRYPT_PREHASH_PREFIX`], which lets
+  /// [`verify_bcrypt`] apply the same transformation.
+  Prehash,
+}
+
+impl BcryptMode {
+  /// Returns the mode a hash was created with
+  pub fn of(hash: &str) -> Self {
+    if hash.starts_with(BCRYPT_PREHASH_PREFIX) {
+      BcryptMode::Prehash
+    } else if hash.starts_with(BCRYPT_STRICT_PREFIX) {
+      BcryptMode::Strict
+    } else {
+      BcryptMode::Truncate
+    }
+  }
+}
+
+fn bcrypt_prehash(password: &str) -> String {
+  let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(BCRYPT_PREHASH_KEY)
+    .expect("HMAC accepts keys of any length");
+  mac.update(password.as_bytes());
+  base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+/// Generates a bcrypt hash, choosing how passwords longer than 72 bytes are handled
+///
+/// # Parameters
+///
+/// * `password` - The password to hash
+/// * `cost` - The cost parameter for hashing, indicating the computational complexity
+/// * `mode` - What to do with passwords longer than 72 bytes
+///
+/// # Returns
+///
+/// A `Result` containing the hash, or a `BcryptError`. In [`BcryptMode::Strict`] a
+/// password longer than 72 bytes returns `BcryptError::Truncation`.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::{bcrypt_with_mode, verify_bcrypt, BcryptMode};
+///
+/// let password = "a".repeat(100);
+/// let hash = bcrypt_with_mode(&password, 4, BcryptMode::Prehash).unwrap();
+/// assert!(verify_bcrypt(&password, &hash).unwrap());
+/// assert!(!verify_bcrypt(&"a".repeat(72), &hash).unwrap());
+///
+/// assert!(bcrypt_with_mode(&password, 4, BcryptMode::Strict).is_err());
+/// ```
+pub fn bcrypt_with_mode(
+  password: &str,
+  cost: u32,
+  mode: BcryptMode,
+) -> Result<String, BcryptError> {
+  match mode {
+    BcryptMode::Truncate => bcrypt::hash(password, cost),
+    BcryptMode::Strict => {
+      let hash = bcrypt::non_truncating_hash(password, cost)?;
+      Ok(format!("{}{}", BCRYPT_STRICT_PREFIX, &hash[1..]))
+    }
+    BcryptMode::Prehash => {
+      let hash = bcrypt::hash(bcrypt_prehash(password), cost)?;
+      Ok(format!("{}{}", BCRYPT_PREHASH_PREFIX, &hash[1..]))
+    }
+  }
+}
+
 /// Verifies a password against a bcrypt hash
 ///
 /// # Parameters
@@ -159,8 +506,21 @@ pub fn bcrypt_custom(
 ///     Err(e) => eprintln!("Verification error: {}", e),
 /// }
 /// ```
+///
+/// # Notes
+///
+/// Hashes created with [`BcryptMode::Prehash`] are detected by their prefix and
+/// the password is prehashed the same way before verification. Against hashes
+/// created with [`BcryptMode::Strict`], a password longer than 72 bytes returns
+/// `BcryptError::Truncation` rather than matching on its first 72 bytes.
 pub fn verify_bcrypt(password: &str, hash: &str) -> Result<bool, bcrypt::BcryptError> {
-  bcrypt::verify(password, hash)
+  if let Some(rest) = hash.strip_prefix(BCRYPT_PREHASH_PREFIX) {
+    bcrypt::verify(bcrypt_prehash(password), &format!("${rest}"))
+  } else if let Some(rest) = hash.strip_prefix(BCRYPT_STRICT_PREFIX) {
+    bcrypt::non_truncating_verify(password, &format!("${rest}"))
+  } else {
+    bcrypt::verify(password, hash)
+  }
 }
 
 /// for password
@@ -233,7 +593,11 @@ pub fn verify_argon2(hash: &str, password: &str) -> Result<bool, argon2::Error>
 
 #[cfg(test)]
 mod tests {
-  use crate::hash::{argon2, bcrypt, bcrypt_custom, blake3, md5, verify_argon2, verify_bcrypt};
+  use crate::hash::{
+    argon2, bcrypt, bcrypt_custom, bcrypt_with_mode, blake3, blake3_reader, md5, sha1, sha256,
+    sha256_reader, sha512, sha512_reader, verify_argon2, verify_bcrypt, xxh64, BcryptError,
+    BcryptMode, BCRYPT_STRICT_PREFIX,
+  };
   #[test]
   fn test_argon2() {
     let password = "my_secure_password";
@@ -253,11 +617,81 @@ mod tests {
     assert!(verify_bcrypt(password, &hashed_password).unwrap());
   }
   #[test]
+  fn test_bcrypt_with_mode() {
+    let long = "x".repeat(80);
+    let truncated = bcrypt_with_mode(&long, 4, BcryptMode::Truncate).unwrap();
+    assert!(verify_bcrypt(&"x".repeat(72), &truncated).unwrap());
+    assert_eq!(BcryptMode::of(&truncated), BcryptMode::Truncate);
+
+    assert!(matches!(
+      bcrypt_with_mode(&long, 4, BcryptMode::Strict),
+      Err(BcryptError::Truncation(_))
+    ));
+    let strict = bcrypt_with_mode("short", 4, BcryptMode::Strict).unwrap();
+    assert_eq!(BcryptMode::of(&strict), BcryptMode::Strict);
+    assert!(verify_bcrypt("short", &strict).unwrap());
+    assert!(!verify_bcrypt("shorter", &strict).unwrap());
+    // A longer password sharing the 72 bytes bcrypt hashes (71 plus the NUL
+    // terminator) would verify with plain truncating bcrypt
+    let exact = "y".repeat(71);
+    let strict = bcrypt_with_mode(&exact, 4, BcryptMode::Strict).unwrap();
+    assert!(verify_bcrypt(&exact, &strict).unwrap());
+    let colliding = format!("{exact}\0suffix");
+    assert!(::bcrypt::verify(
+      &colliding,
+      &format!("${}", &strict[BCRYPT_STRICT_PREFIX.len()..])
+    )
+    .unwrap());
+    assert!(matches!(
+      verify_bcrypt(&colliding, &strict),
+      Err(BcryptError::Truncation(_))
+    ));
+
+    let prehashed = bcrypt_with_mode(&long, 4, BcryptMode::Prehash).unwrap();
+    assert_eq!(BcryptMode::of(&prehashed), BcryptMode::Prehash);
+    assert!(verify_bcrypt(&long, &prehashed).unwrap());
+    assert!(!verify_bcrypt(&"x".repeat(72), &prehashed).unwrap());
+    assert!(!verify_bcrypt(&"x".repeat(81), &prehashed).unwrap());
+  }
+  #[test]
   fn test_md5() {
     let data = "hello world";
     assert_eq!(md5(data), md5(data));
   }
   #[test]
+  fn test_sha1() {
+    assert_eq!(sha1(b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+  }
+  #[test]
+  fn test_sha2() {
+    assert_eq!(
+      sha256(b"abc"),
+      "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+      sha512(b"abc"),
+      "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+       2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+    );
+  }
+  #[test]
+  fn test_readers() {
+    let data = vec![7u8; 200_000];
+    assert_eq!(sha256_reader(&data[..]).unwrap(), sha256(&data));
+    assert_eq!(sha512_reader(&data[..]).unwrap(), sha512(&data));
+    assert_eq!(blake3_reader(&data[..]).unwrap(), blake3(&data));
+  }
+  #[test]
+  fn test_xxh64() {
+    assert_eq!(xxh64(b"", 0), 0xef46db3751d8e999);
+    assert_eq!(xxh64(b"abc", 0), 0x44bc2cf5ad770999);
+    assert_eq!(
+      xxh64(b"Nobody inspects the spammish repetition", 0),
+      0xfbcea83c8a378bf1
+    );
+    assert_ne!(xxh64(b"abc", 1), xxh64(b"abc", 0));
+  }
+  #[test]
   fn test_blake3() {
     let data = b"hello world";
     let hased = blake3(data);
@@ -0,0 +1,309 @@
+//! Content-defined chunking based on FastCDC
+//!
+//! Splits a byte stream into variable-sized chunks whose boundaries depend on the
+//! content itself, so an insertion near the start of a file only changes the chunks
+//! around it. Every chunk is digested with [`blake3`](super::blake3), which makes the
+//! output directly usable as keys of a deduplicating store.
+
+use std::io::{self, Read};
+
+/// Default minimum chunk size (2 KiB)
+pub const DEFAULT_MIN_SIZE: usize = 2 * 1024;
+
+/// Default average chunk size (8 KiB)
+pub const DEFAULT_AVG_SIZE: usize = 8 * 1024;
+
+/// Default maximum chunk size (64 KiB)
+pub const DEFAULT_MAX_SIZE: usize = 64 * 1024;
+
+/// Gear table used by the rolling hash. It is generated from a fixed seed with
+/// splitmix64, so the chunk boundaries are stable across builds and platforms.
+const GEAR: [u64; 256] = {
+  let mut table = [0u64; 256];
+  let mut state: u64 = 0x6a09_e667_f3bc_c908;
+  let mut i = 0;
+  while i < 256 {
+    state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    table[i] = z ^ (z >> 31);
+    i += 1;
+  }
+  table
+};
+
+/// A single content-defined chunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+  /// Position of the first byte of the chunk in the stream
+  pub offset: u64,
+  /// Length of the chunk in bytes
+  pub length: usize,
+  /// BLAKE3 digest of the chunk, as a hexadecimal string
+  pub digest: String,
+}
+
+/// FastCDC chunker configuration
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::cdc::FastCdc;
+///
+/// let data = vec![0u8; 100 * 1024];
+/// let chunker = FastCdc::new(1024, 4096, 16384);
+/// for chunk in chunker.chunks(&data[..]) {
+///   let chunk = chunk.unwrap();
+///   println!("{} {} {}", chunk.offset, chunk.length, chunk.digest);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastCdc {
+  min_size: usize,
+  avg_size: usize,
+  max_size: usize,
+  mask_s: u64,
+  mask_l: u64,
+}
+
+impl Default for FastCdc {
+  fn default() -> Self {
+    Self::new(DEFAULT_MIN_SIZE, DEFAULT_AVG_SIZE, DEFAULT_MAX_SIZE)
+  }
+}
+
+impl FastCdc {
+  /// Creates a chunker with the given minimum, average and maximum chunk sizes
+  ///
+  /// # Parameters
+  ///
+  /// * `min_size` - Chunks are never cut before this many bytes (except the last one)
+  /// * `avg_size` - Expected chunk size, rounded to the nearest power of two internally
+  /// * `max_size` - Chunks are always cut at this many bytes
+  ///
+  /// # Panics
+  ///
+  /// Panics unless `0 < min_size <= avg_size <= max_size`
+  pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+    assert!(min_size > 0, "min_size must be greater than zero");
+    assert!(
+      min_size <= avg_size && avg_size <= max_size,
+      "chunk sizes must satisfy min_size <= avg_size <= max_size"
+    );
+    let bits = (avg_size as f64).log2().round() as u32;
+    // Normalized chunking: a harder mask before the average size and an easier
+    // one after it, which narrows the chunk size distribution.
+    Self {
+      min_size,
+      avg_size,
+      max_size,
+      mask_s: high_bits_mask(bits + 1),
+      mask_l: high_bits_mask(bits.saturating_sub(1)),
+    }
+  }
+
+  /// Minimum chunk size in bytes
+  pub fn min_size(&self) -> usize {
+    self.min_size
+  }
+
+  /// Average chunk size in bytes
+  pub fn avg_size(&self) -> usize {
+    self.avg_size
+  }
+
+  /// Maximum chunk size in bytes
+  pub fn max_size(&self) -> usize {
+    self.max_size
+  }
+
+  /// Returns the length of the first chunk found at the start of `data`
+  ///
+  /// `data` is treated as the remaining stream, so if no boundary is found the
+  /// whole input (capped at `max_size`) is returned.
+  pub fn cut(&self, data: &[u8]) -> usize {
+    let len = data.len();
+    if len <= self.min_size {
+      return len;
+    }
+    let end = len.min(self.max_size);
+    let normal = self.avg_size.min(end);
+    let mut hash: u64 = 0;
+    let mut i = self.min_size;
+    while i < normal {
+      hash = (hash << 1).wrapping_add(GEAR[data[i] as usize]);
+      if hash & self.mask_s == 0 {
+        return i + 1;
+      }
+      i += 1;
+    }
+    while i < end {
+      hash = (hash << 1).wrapping_add(GEAR[data[i] as usize]);
+      if hash & self.mask_l == 0 {
+        return i + 1;
+      }
+      i += 1;
+    }
+    end
+  }
+
+  /// Splits the stream read from `reader` into chunks
+  ///
+  /// # Returns
+  ///
+  /// An iterator yielding a [`Chunk`] for every cut, or the I/O error that stopped it
+  pub fn chunks<R: Read>(&self, reader: R) -> Chunks<R> {
+    Chunks {
+      config: *self,
+      reader,
+      buf: vec![0; self.max_size],
+      start: 0,
+      end: 0,
+      offset: 0,
+      eof: false,
+    }
+  }
+}
+
+fn high_bits_mask(bits: u32) -> u64 {
+  match bits {
+    0 => 0,
+    64.. => u64::MAX,
+    _ => u64::MAX << (64 - bits),
+  }
+}
+
+/// Iterator over the chunks of a stream, created by [`FastCdc::chunks`]
+#[derive(Debug)]
+pub struct Chunks<R> {
+  config: FastCdc,
+  reader: R,
+  buf: Vec<u8>,
+  start: usize,
+  end: usize,
+  offset: u64,
+  eof: bool,
+}
+
+impl<R: Read> Chunks<R> {
+  fn fill(&mut self) -> io::Result<()> {
+    if self.start > 0 {
+      self.buf.copy_within(self.start..self.end, 0);
+      self.end -= self.start;
+      self.start = 0;
+    }
+    while !self.eof && self.end < self.buf.len() {
+      match self.reader.read(&mut self.buf[self.end..]) {
+        Ok(0) => self.eof = true,
+        Ok(n) => self.end += n,
+        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+        Err(e) => return Err(e),
+      }
+    }
+    Ok(())
+  }
+}
+
+impl<R: Read> Iterator for Chunks<R> {
+  type Item = io::Result<Chunk>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.end - self.start < self.config.max_size {
+      if let Err(e) = self.fill() {
+        return Some(Err(e));
+      }
+    }
+    if self.start == self.end {
+      return None;
+    }
+    let data = &self.buf[self.start..self.end];
+    let length = self.config.cut(data);
+    let chunk = Chunk {
+      offset: self.offset,
+      length,
+      digest: super::blake3(&data[..length]),
+    };
+    self.start += length;
+    self.offset += length as u64;
+    Some(Ok(chunk))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::hash::blake3;
+  use crate::hash::cdc::FastCdc;
+
+  fn sample(len: usize) -> Vec<u8> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..len)
+      .map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 56) as u8
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_chunks_cover_input() {
+    let data = sample(200_000);
+    let chunker = FastCdc::new(1024, 4096, 16384);
+    let chunks: Vec<_> = chunker.chunks(&data[..]).map(Result::unwrap).collect();
+    let mut offset = 0;
+    for (i, chunk) in chunks.iter().enumerate() {
+      assert_eq!(chunk.offset, offset as u64);
+      assert!(chunk.length <= 16384);
+      if i + 1 < chunks.len() {
+        assert!(chunk.length >= 1024);
+      }
+      assert_eq!(chunk.digest, blake3(&data[offset..offset + chunk.length]));
+      offset += chunk.length;
+    }
+    assert_eq!(offset, data.len());
+  }
+
+  #[test]
+  fn test_chunks_pinned() {
+    let data = sample(64 * 1024);
+    let chunker = FastCdc::new(1024, 4096, 16384);
+    let lengths: Vec<_> = chunker
+      .chunks(&data[..])
+      .map(|c| c.unwrap().length)
+      .collect();
+    assert_eq!(
+      lengths,
+      [5052, 4413, 2240, 5218, 4487, 2026, 8651, 4788, 1720, 5197, 7930, 1131, 4123, 4385, 4175]
+    );
+  }
+
+  #[test]
+  fn test_chunks_shift_resistant() {
+    let data = sample(100_000);
+    let mut shifted = b"inserted prefix".to_vec();
+    shifted.extend_from_slice(&data);
+    let chunker = FastCdc::new(512, 2048, 8192);
+    let a: Vec<_> = chunker
+      .chunks(&data[..])
+      .map(|c| c.unwrap().digest)
+      .collect();
+    let b: Vec<_> = chunker
+      .chunks(&shifted[..])
+      .map(|c| c.unwrap().digest)
+      .collect();
+    let shared = a.iter().filter(|d| b.contains(d)).count();
+    assert!(shared + 2 >= a.len());
+  }
+
+  #[test]
+  fn test_small_input() {
+    let chunker = FastCdc::default();
+    assert_eq!(chunker.chunks(&[][..]).count(), 0);
+    let chunks: Vec<_> = chunker.chunks(&b"hello"[..]).map(Result::unwrap).collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].length, 5);
+    assert_eq!(chunks[0].digest, blake3(b"hello"));
+  }
+}
@@ -0,0 +1,760 @@
+//! Probabilistic membership filters
+//!
+//! [`BloomFilter`] and [`CuckooFilter`] answer "definitely not present" or
+//! "probably present" in constant time and a few bits per item, which keeps
+//! lookups of unknown IDs away from the database. Both hash with
+//! [`xxh64`](super::xxh64) and can be stored as bytes.
+
+use std::error::Error;
+use std::fmt;
+
+use super::xxh64;
+
+const BLOOM_MAGIC: &[u8; 4] = b"BLM1";
+const CUCKOO_MAGIC: &[u8; 4] = b"CKO1";
+const BUCKET_SIZE: usize = 4;
+const MAX_KICKS: usize = 500;
+const FINGERPRINT_SEED: u64 = 0x5bd1_e995;
+
+/// Errors returned by the filters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterError {
+  /// The two filters were not created with the same parameters
+  Incompatible,
+  /// The bytes do not describe a valid filter
+  InvalidBytes,
+  /// The cuckoo filter has no room left for the item
+  Full,
+}
+
+impl fmt::Display for FilterError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FilterError::Incompatible => write!(f, "filters have different parameters"),
+      FilterError::InvalidBytes => write!(f, "invalid filter bytes"),
+      FilterError::Full => write!(f, "filter is full"),
+    }
+  }
+}
+
+impl Error for FilterError {}
+
+/// A Bloom filter using double hashing
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::filter::BloomFilter;
+///
+/// let mut seen = BloomFilter::new(10_000, 0.01);
+/// seen.insert("user-1");
+/// assert!(seen.contains("user-1"));
+///
+/// let restored = BloomFilter::from_bytes(&seen.to_bytes()).unwrap();
+/// assert!(restored.contains("user-1"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+  bits: Vec<u64>,
+  num_bits: u64,
+  num_hashes: u32,
+  count: u64,
+}
+
+impl BloomFilter {
+  /// Creates a filter sized for `expected_items` with the target false positive rate
+  ///
+  /// # Panics
+  ///
+  /// Panics if `fp_rate` is not strictly between 0 and 1
+  pub fn new(expected_items: usize, fp_rate: f64) -> Self {
+    assert!(
+      fp_rate > 0.0 && fp_rate < 1.0,
+      "fp_rate must be between 0 and 1"
+    );
+    let n = expected_items.max(1) as f64;
+    let ln2 = std::f64::consts::LN_2;
+    let num_bits = (-n * fp_rate.ln() / (ln2 * ln2)).ceil().max(64.0) as u64;
+    let num_hashes = ((num_bits as f64 / n) * ln2).round().clamp(1.0, 32.0) as u32;
+    Self::with_params(num_bits, num_hashes)
+  }
+
+  /// Creates a filter with an explicit number of bits and hash functions
+  pub fn with_params(num_bits: u64, num_hashes: u32) -> Self {
+    let num_bits = num_bits.max(1);
+    Self {
+      bits: vec![0; num_bits.div_ceil(64) as usize],
+      num_bits,
+      num_hashes: num_hashes.max(1),
+      count: 0,
+    }
+  }
+
+  fn indexes(&self, item: &[u8]) -> impl Iterator<Item = u64> {
+    let h1 = xxh64(item, 0);
+    let h2 = xxh64(item, h1) | 1;
+    let num_bits = self.num_bits;
+    (0..self.num_hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % num_bits)
+  }
+
+  /// Adds an item, returning `false` if it was probably present already
+  pub fn insert<T: AsRef<[u8]>>(&mut self, item: T) -> bool {
+    let mut added = false;
+    let indexes: Vec<u64> = self.indexes(item.as_ref()).collect();
+    for index in indexes {
+      let (word, mask) = ((index / 64) as usize, 1u64 << (index % 64));
+      if self.bits[word] & mask == 0 {
+        self.bits[word] |= mask;
+        added = true;
+      }
+    }
+    if added {
+      self.count += 1;
+    }
+    added
+  }
+
+  /// Returns `false` if the item is definitely absent, `true` if probably present
+  pub fn contains<T: AsRef<[u8]>>(&self, item: T) -> bool {
+    self
+      .indexes(item.as_ref())
+      .all(|index| self.bits[(index / 64) as usize] & (1u64 << (index % 64)) != 0)
+  }
+
+  /// Number of items inserted, not counting probable duplicates
+  pub fn len(&self) -> u64 {
+    self.count
+  }
+
+  /// Returns `true` if no item has been inserted
+  pub fn is_empty(&self) -> bool {
+    self.bits.iter().all(|&w| w == 0)
+  }
+
+  /// Number of bits in the filter
+  pub fn num_bits(&self) -> u64 {
+    self.num_bits
+  }
+
+  /// Number of hash functions
+  pub fn num_hashes(&self) -> u32 {
+    self.num_hashes
+  }
+
+  /// Current false positive probability, estimated from the fraction of set bits
+  pub fn estimated_fp_rate(&self) -> f64 {
+    let set: u64 = self.bits.iter().map(|w| w.count_ones() as u64).sum();
+    (set as f64 / self.num_bits as f64).powi(self.num_hashes as i32)
+  }
+
+  /// Removes every item
+  pub fn clear(&mut self) {
+    self.bits.iter_mut().for_each(|w| *w = 0);
+    self.count = 0;
+  }
+
+  /// Adds every item of `other` to this filter
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FilterError::Incompatible`] unless both filters have the same size
+  /// and number of hash functions
+  pub fn union(&mut self, other: &BloomFilter) -> Result<(), FilterError> {
+    self.check_compatible(other)?;
+    for (a, b) in self.bits.iter_mut().zip(&other.bits) {
+      *a |= b;
+    }
+    self.count = self.estimate_count();
+    Ok(())
+  }
+
+  /// Keeps only the items probably present in both filters
+  ///
+  /// The result may have a higher false positive rate than a filter built from
+  /// the actual intersection.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FilterError::Incompatible`] unless both filters have the same size
+  /// and number of hash functions
+  pub fn intersection(&mut self, other: &BloomFilter) -> Result<(), FilterError> {
+    self.check_compatible(other)?;
+    for (a, b) in self.bits.iter_mut().zip(&other.bits) {
+      *a &= b;
+    }
+    self.count = self.estimate_count();
+    Ok(())
+  }
+
+  fn check_compatible(&self, other: &BloomFilter) -> Result<(), FilterError> {
+    if self.num_bits == other.num_bits && self.num_hashes == other.num_hashes {
+      Ok(())
+    } else {
+      Err(FilterError::Incompatible)
+    }
+  }
+
+  /// Swamidass & Baldi estimate of the number of items from the set bits
+  fn estimate_count(&self) -> u64 {
+    let m = self.num_bits as f64;
+    let set: u64 = self.bits.iter().map(|w| w.count_ones() as u64).sum();
+    if set as f64 >= m {
+      return u64::MAX;
+    }
+    (-(m / self.num_hashes as f64) * (1.0 - set as f64 / m).ln()).round() as u64
+  }
+
+  /// Serializes the filter
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut out = Vec::with_capacity(24 + self.bits.len() * 8);
+    out.extend_from_slice(BLOOM_MAGIC);
+    out.extend_from_slice(&self.num_bits.to_le_bytes());
+    out.extend_from_slice(&self.num_hashes.to_le_bytes());
+    out.extend_from_slice(&self.count.to_le_bytes());
+    for word in &self.bits {
+      out.extend_from_slice(&word.to_le_bytes());
+    }
+    out
+  }
+
+  /// Restores a filter written by [`to_bytes`](Self::to_bytes)
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FilterError::InvalidBytes`] if the data is truncated or malformed
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, FilterError> {
+    let mut reader = ByteReader::new(bytes, BLOOM_MAGIC)?;
+    let num_bits = reader.u64()?;
+    let num_hashes = reader.u32()?;
+    let count = reader.u64()?;
+    if num_bits == 0 || num_hashes == 0 {
+      return Err(FilterError::InvalidBytes);
+    }
+    let words = num_bits.div_ceil(64) as usize;
+    if reader.remaining() != words * 8 {
+      return Err(FilterError::InvalidBytes);
+    }
+    let bits = (0..words).map(|_| reader.u64()).collect::<Result<_, _>>()?;
+    Ok(Self {
+      bits,
+      num_bits,
+      num_hashes,
+      count,
+    })
+  }
+}
+
+/// A cuckoo filter with four-slot buckets and partial-key cuckoo hashing
+///
+/// Unlike a Bloom filter it supports deleting items, which suits sets such as
+/// revoked tokens that expire.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::filter::CuckooFilter;
+///
+/// let mut revoked = CuckooFilter::new(10_000, 0.001);
+/// revoked.insert("token-a").unwrap();
+/// assert!(revoked.contains("token-a"));
+/// assert!(revoked.remove("token-a"));
+/// assert!(!revoked.contains("token-a"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CuckooFilter {
+  slots: Vec<u32>,
+  num_buckets: usize,
+  fingerprint_bits: u32,
+  count: u64,
+  victim: Option<(usize, u32)>,
+}
+
+impl CuckooFilter {
+  /// Creates a filter sized for `expected_items` with the target false positive rate
+  ///
+  /// # Panics
+  ///
+  /// Panics if `fp_rate` is not strictly between 0 and 1
+  pub fn new(expected_items: usize, fp_rate: f64) -> Self {
+    assert!(
+      fp_rate > 0.0 && fp_rate < 1.0,
+      "fp_rate must be between 0 and 1"
+    );
+    let fingerprint_bits = (2.0 * BUCKET_SIZE as f64 / fp_rate)
+      .log2()
+      .ceil()
+      .clamp(4.0, 32.0) as u32;
+    // Cuckoo filters with four-slot buckets reliably reach about 95% occupancy.
+    let buckets = (expected_items.max(1) as f64 / (BUCKET_SIZE as f64 * 0.95)).ceil() as usize;
+    Self::with_params(buckets, fingerprint_bits)
+  }
+
+  /// Creates a filter with an explicit number of buckets and fingerprint size
+  ///
+  /// The number of buckets is rounded up to a power of two and the fingerprint
+  /// size is clamped to 4..=32 bits.
+  pub fn with_params(num_buckets: usize, fingerprint_bits: u32) -> Self {
+    let num_buckets = num_buckets.max(1).next_power_of_two();
+    Self {
+      slots: vec![0; num_buckets * BUCKET_SIZE],
+      num_buckets,
+      fingerprint_bits: fingerprint_bits.clamp(4, 32),
+      count: 0,
+      victim: None,
+    }
+  }
+
+  fn fingerprint(&self, item: &[u8]) -> u32 {
+    let mask = if self.fingerprint_bits == 32 {
+      u32::MAX
+    } else {
+      (1u32 << self.fingerprint_bits) - 1
+    };
+    // Zero marks an empty slot.
+    match xxh64(item, FINGERPRINT_SEED) as u32 & mask {
+      0 => 1,
+      fp => fp,
+    }
+  }
+
+  fn index(&self, item: &[u8]) -> usize {
+    xxh64(item, 0) as usize & (self.num_buckets - 1)
+  }
+
+  fn alt_index(&self, index: usize, fingerprint: u32) -> usize {
+    (index ^ xxh64(&fingerprint.to_le_bytes(), 0) as usize) & (self.num_buckets - 1)
+  }
+
+  fn bucket(&self, index: usize) -> &[u32] {
+    &self.slots[index * BUCKET_SIZE..(index + 1) * BUCKET_SIZE]
+  }
+
+  fn bucket_mut(&mut self, index: usize) -> &mut [u32] {
+    &mut self.slots[index * BUCKET_SIZE..(index + 1) * BUCKET_SIZE]
+  }
+
+  fn try_put(&mut self, index: usize, fingerprint: u32) -> bool {
+    match self.bucket_mut(index).iter_mut().find(|slot| **slot == 0) {
+      Some(slot) => {
+        *slot = fingerprint;
+        true
+      }
+      None => false,
+    }
+  }
+
+  fn has(&self, index: usize, fingerprint: u32) -> bool {
+    let alt = self.alt_index(index, fingerprint);
+    self.bucket(index).contains(&fingerprint)
+      || self.bucket(alt).contains(&fingerprint)
+      || self
+        .victim
+        .is_some_and(|(i, fp)| fp == fingerprint && (i == index || i == alt))
+  }
+
+  /// Adds an item
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FilterError::Full`] when no slot can be freed for the item, in
+  /// which case the filter should be rebuilt with a larger capacity
+  pub fn insert<T: AsRef<[u8]>>(&mut self, item: T) -> Result<(), FilterError> {
+    let item = item.as_ref();
+    let fingerprint = self.fingerprint(item);
+    let index = self.index(item);
+    self.insert_fingerprint(index, fingerprint)
+  }
+
+  fn insert_fingerprint(&mut self, index: usize, fingerprint: u32) -> Result<(), FilterError> {
+    if self.victim.is_some() {
+      return Err(FilterError::Full);
+    }
+    let alt = self.alt_index(index, fingerprint);
+    if self.try_put(index, fingerprint) || self.try_put(alt, fingerprint) {
+      self.count += 1;
+      return Ok(());
+    }
+    let mut index = if fingerprint & 1 == 0 { index } else { alt };
+    let mut fingerprint = fingerprint;
+    for kick in 0..MAX_KICKS {
+      let slot = (xxh64(&fingerprint.to_le_bytes(), kick as u64) as usize) % BUCKET_SIZE;
+      std::mem::swap(&mut fingerprint, &mut self.bucket_mut(index)[slot]);
+      index = self.alt_index(index, fingerprint);
+      if self.try_put(index, fingerprint) {
+        self.count += 1;
+        return Ok(());
+      }
+    }
+    // Keep the evicted fingerprint so no previously inserted item is lost.
+    self.victim = Some((index, fingerprint));
+    self.count += 1;
+    Ok(())
+  }
+
+  /// Returns `false` if the item is definitely absent, `true` if probably present
+  pub fn contains<T: AsRef<[u8]>>(&self, item: T) -> bool {
+    let item = item.as_ref();
+    self.has(self.index(item), self.fingerprint(item))
+  }
+
+  /// Removes one occurrence of an item, returning `true` if it was found
+  ///
+  /// Only remove items that were inserted, otherwise another item sharing the
+  /// same fingerprint may be removed instead.
+  pub fn remove<T: AsRef<[u8]>>(&mut self, item: T) -> bool {
+    let item = item.as_ref();
+    let fingerprint = self.fingerprint(item);
+    let index = self.index(item);
+    self.remove_fingerprint(index, fingerprint)
+  }
+
+  fn remove_fingerprint(&mut self, index: usize, fingerprint: u32) -> bool {
+    let alt = self.alt_index(index, fingerprint);
+    if let Some((i, fp)) = self.victim {
+      if fp == fingerprint && (i == index || i == alt) {
+        self.victim = None;
+        self.count -= 1;
+        return true;
+      }
+    }
+    for i in [index, alt] {
+      if let Some(slot) = self.bucket_mut(i).iter_mut().find(|s| **s == fingerprint) {
+        *slot = 0;
+        self.count -= 1;
+        self.reinsert_victim();
+        return true;
+      }
+    }
+    false
+  }
+
+  fn reinsert_victim(&mut self) {
+    if let Some((index, fingerprint)) = self.victim.take() {
+      self.count -= 1;
+      // Cannot fail: the victim is cleared and a slot was just freed.
+      let _ = self.insert_fingerprint(index, fingerprint);
+    }
+  }
+
+  /// Number of items stored
+  pub fn len(&self) -> u64 {
+    self.count
+  }
+
+  /// Returns `true` if the filter holds no item
+  pub fn is_empty(&self) -> bool {
+    self.count == 0
+  }
+
+  /// Maximum number of items the filter can hold
+  pub fn capacity(&self) -> usize {
+    self.slots.len()
+  }
+
+  /// Size of a fingerprint in bits
+  pub fn fingerprint_bits(&self) -> u32 {
+    self.fingerprint_bits
+  }
+
+  /// Upper bound of the false positive rate at the current load
+  pub fn estimated_fp_rate(&self) -> f64 {
+    let load = self.count as f64 / self.slots.len() as f64;
+    (2.0 * BUCKET_SIZE as f64 * load / 2f64.powi(self.fingerprint_bits as i32)).min(1.0)
+  }
+
+  fn stored(&self) -> Vec<(usize, u32)> {
+    let mut stored: Vec<(usize, u32)> = self
+      .slots
+      .iter()
+      .enumerate()
+      .filter(|(_, &fp)| fp != 0)
+      .map(|(i, &fp)| (i / BUCKET_SIZE, fp))
+      .collect();
+    stored.extend(self.victim);
+    stored
+  }
+
+  /// Adds the items of `other` that are not already present
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FilterError::Incompatible`] unless both filters have the same
+  /// parameters, or [`FilterError::Full`] when this filter runs out of room
+  pub fn union(&mut self, other: &CuckooFilter) -> Result<(), FilterError> {
+    self.check_compatible(other)?;
+    for (index, fingerprint) in other.stored() {
+      if !self.has(index, fingerprint) {
+        self.insert_fingerprint(index, fingerprint)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Keeps only the items also present in `other`
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FilterError::Incompatible`] unless both filters have the same parameters
+  pub fn intersection(&mut self, other: &CuckooFilter) -> Result<(), FilterError> {
+    self.check_compatible(other)?;
+    for (index, fingerprint) in self.stored() {
+      if !other.has(index, fingerprint) {
+        self.remove_fingerprint(index, fingerprint);
+      }
+    }
+    Ok(())
+  }
+
+  fn check_compatible(&self, other: &CuckooFilter) -> Result<(), FilterError> {
+    if self.num_buckets == other.num_buckets && self.fingerprint_bits == other.fingerprint_bits {
+      Ok(())
+    } else {
+      Err(FilterError::Incompatible)
+    }
+  }
+
+  /// Serializes the filter
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut out = Vec::with_capacity(40 + self.slots.len() * 4);
+    out.extend_from_slice(CUCKOO_MAGIC);
+    out.extend_from_slice(&(self.num_buckets as u64).to_le_bytes());
+    out.extend_from_slice(&self.fingerprint_bits.to_le_bytes());
+    out.extend_from_slice(&self.count.to_le_bytes());
+    let (victim_index, victim_fp) = self.victim.unwrap_or((0, 0));
+    out.extend_from_slice(&(victim_index as u64).to_le_bytes());
+    out.extend_from_slice(&victim_fp.to_le_bytes());
+    for slot in &self.slots {
+      out.extend_from_slice(&slot.to_le_bytes());
+    }
+    out
+  }
+
+  /// Restores a filter written by [`to_bytes`](Self::to_bytes)
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FilterError::InvalidBytes`] if the data is truncated or malformed
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, FilterError> {
+    let mut reader = ByteReader::new(bytes, CUCKOO_MAGIC)?;
+    let num_buckets = usize::try_from(reader.u64()?).map_err(|_| FilterError::InvalidBytes)?;
+    let fingerprint_bits = reader.u32()?;
+    let count = reader.u64()?;
+    let victim_index = usize::try_from(reader.u64()?).map_err(|_| FilterError::InvalidBytes)?;
+    let victim_fp = reader.u32()?;
+    let num_slots = num_buckets
+      .checked_mul(BUCKET_SIZE)
+      .ok_or(FilterError::InvalidBytes)?;
+    if !num_buckets.is_power_of_two()
+      || !(4..=32).contains(&fingerprint_bits)
+      || victim_index >= num_buckets
+      || Some(reader.remaining()) != num_slots.checked_mul(4)
+    {
+      return Err(FilterError::InvalidBytes);
+    }
+    let slots: Vec<u32> = (0..num_slots)
+      .map(|_| reader.u32())
+      .collect::<Result<_, _>>()?;
+    // Removing decrements the count, so it must match what is actually stored.
+    let max_fp = u32::MAX >> (32 - fingerprint_bits);
+    let stored = slots.iter().chain([&victim_fp]).filter(|fp| **fp != 0);
+    if stored.clone().any(|fp| *fp > max_fp) || stored.count() as u64 != count {
+      return Err(FilterError::InvalidBytes);
+    }
+    Ok(Self {
+      slots,
+      num_buckets,
+      fingerprint_bits,
+      count,
+      victim: (victim_fp != 0).then_some((victim_index, victim_fp)),
+    })
+  }
+}
+
+struct ByteReader<'a> {
+  bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+  fn new(bytes: &'a [u8], magic: &[u8; 4]) -> Result<Self, FilterError> {
+    match bytes.strip_prefix(magic.as_slice()) {
+      Some(bytes) => Ok(Self { bytes }),
+      None => Err(FilterError::InvalidBytes),
+    }
+  }
+
+  fn take<const N: usize>(&mut self) -> Result<[u8; N], FilterError> {
+    let (head, rest) = self
+      .bytes
+      .split_first_chunk::<N>()
+      .ok_or(FilterError::InvalidBytes)?;
+    self.bytes = rest;
+    Ok(*head)
+  }
+
+  fn u32(&mut self) -> Result<u32, FilterError> {
+    self.take().map(u32::from_le_bytes)
+  }
+
+  fn u64(&mut self) -> Result<u64, FilterError> {
+    self.take().map(u64::from_le_bytes)
+  }
+
+  fn remaining(&self) -> usize {
+    self.bytes.len()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::hash::filter::{BloomFilter, CuckooFilter, FilterError};
+
+  fn false_positive_rate(contains: impl Fn(&str) -> bool) -> f64 {
+    let trials = 20_000;
+    let hits = (0..trials)
+      .filter(|i| contains(&format!("absent-{i}")))
+      .count();
+    hits as f64 / trials as f64
+  }
+
+  #[test]
+  fn test_bloom() {
+    let mut filter = BloomFilter::new(5_000, 0.01);
+    assert!(filter.is_empty());
+    for i in 0..5_000 {
+      filter.insert(format!("item-{i}"));
+    }
+    for i in 0..5_000 {
+      assert!(filter.contains(format!("item-{i}")));
+    }
+    let rate = false_positive_rate(|item| filter.contains(item));
+    assert!(rate < 0.02, "false positive rate {rate}");
+    assert!((filter.estimated_fp_rate() - 0.01).abs() < 0.005);
+  }
+
+  #[test]
+  fn test_bloom_bytes() {
+    let mut filter = BloomFilter::new(100, 0.01);
+    filter.insert("a");
+    filter.insert(b"b");
+    let bytes = filter.to_bytes();
+    assert_eq!(BloomFilter::from_bytes(&bytes).unwrap(), filter);
+    assert_eq!(
+      BloomFilter::from_bytes(&bytes[..bytes.len() - 1]),
+      Err(FilterError::InvalidBytes)
+    );
+    assert_eq!(
+      BloomFilter::from_bytes(b"nope"),
+      Err(FilterError::InvalidBytes)
+    );
+  }
+
+  #[test]
+  fn test_bloom_set_operations() {
+    let mut a = BloomFilter::new(1_000, 0.001);
+    let mut b = BloomFilter::new(1_000, 0.001);
+    a.insert("only-a");
+    a.insert("both");
+    b.insert("only-b");
+    b.insert("both");
+
+    let mut union = a.clone();
+    union.union(&b).unwrap();
+    assert!(union.contains("only-a") && union.contains("only-b") && union.contains("both"));
+    assert_eq!(union.len(), 3);
+
+    a.intersection(&b).unwrap();
+    assert!(a.contains("both"));
+    assert!(!a.contains("only-a") && !a.contains("only-b"));
+
+    let other = BloomFilter::new(10, 0.1);
+    assert_eq!(a.union(&other), Err(FilterError::Incompatible));
+  }
+
+  #[test]
+  fn test_cuckoo() {
+    let mut filter = CuckooFilter::new(5_000, 0.01);
+    for i in 0..5_000 {
+      filter.insert(format!("item-{i}")).unwrap();
+    }
+    assert_eq!(filter.len(), 5_000);
+    for i in 0..5_000 {
+      assert!(filter.contains(format!("item-{i}")));
+    }
+    let rate = false_positive_rate(|item| filter.contains(item));
+    assert!(rate < 0.02, "false positive rate {rate}");
+
+    for i in 0..2_500 {
+      assert!(filter.remove(format!("item-{i}")));
+    }
+    assert_eq!(filter.len(), 2_500);
+    for i in 2_500..5_000 {
+      assert!(filter.contains(format!("item-{i}")));
+    }
+  }
+
+  #[test]
+  fn test_cuckoo_full() {
+    let mut filter = CuckooFilter::with_params(4, 16);
+    let mut inserted = 0;
+    while filter.insert(format!("item-{inserted}")).is_ok() {
+      inserted += 1;
+    }
+    assert!(inserted >= 12 && inserted <= filter.capacity() + 1);
+    for i in 0..inserted {
+      assert!(filter.contains(format!("item-{i}")));
+    }
+    assert!(filter.remove("item-0"));
+    assert!(filter.insert("again").is_ok());
+  }
+
+  #[test]
+  fn test_cuckoo_bytes_and_set_operations() {
+    let mut a = CuckooFilter::new(1_000, 0.001);
+    let mut b = CuckooFilter::new(1_000, 0.001);
+    a.insert("only-a").unwrap();
+    a.insert("both").unwrap();
+    b.insert("only-b").unwrap();
+    b.insert("both").unwrap();
+
+    let restored = CuckooFilter::from_bytes(&a.to_bytes()).unwrap();
+    assert_eq!(restored, a);
+    assert_eq!(
+      CuckooFilter::from_bytes(&[0; 8]),
+      Err(FilterError::InvalidBytes)
+    );
+
+    // Header fields sit after the 4-byte magic: buckets, fingerprint bits,
+    // count, victim index and victim fingerprint.
+    let bytes = a.to_bytes();
+    let tampered = |offset: usize, value: &[u8]| {
+      let mut bytes = bytes.clone();
+      bytes[offset..offset + value.len()].copy_from_slice(value);
+      CuckooFilter::from_bytes(&bytes)
+    };
+    for bad in [
+      tampered(4, &(1u64 << 62).to_le_bytes()),
+      tampered(16, &0u64.to_le_bytes()),
+      tampered(16, &3u64.to_le_bytes()),
+      tampered(32, &1u32.to_le_bytes()),
+    ] {
+      assert_eq!(bad, Err(FilterError::InvalidBytes));
+    }
+    let slot = 36 + bytes[36..].chunks(4).position(|c| c != [0; 4]).unwrap() * 4;
+    assert_eq!(
+      tampered(slot, &u32::MAX.to_le_bytes()),
+      Err(FilterError::InvalidBytes)
+    );
+
+    let mut union = a.clone();
+    union.union(&b).unwrap();
+    assert_eq!(union.len(), 3);
+    assert!(union.contains("only-a") && union.contains("only-b") && union.contains("both"));
+
+    a.intersection(&b).unwrap();
+    assert_eq!(a.len(), 1);
+    assert!(a.contains("both") && !a.contains("only-a"));
+
+    let other = CuckooFilter::new(10, 0.1);
+    assert_eq!(a.intersection(&other), Err(FilterError::Incompatible));
+  }
+}
@@ -0,0 +1,342 @@
+//! File integrity manifests compatible with `sha256sum`, `sha512sum` and `b3sum`
+//!
+//! A manifest lists one `<digest>  <path>` line per regular file under a root
+//! directory, with paths relative to the root and `/` as separator. Files are
+//! hashed in parallel with the streaming hashers of this module.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+
+use rayon::prelude::*;
+
+/// Digest algorithm used by a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+  /// SHA-256, as written by `sha256sum`
+  Sha256,
+  /// SHA-512, as written by `sha512sum`
+  Sha512,
+  /// BLAKE3, as written by `b3sum`
+  Blake3,
+}
+
+impl Algorithm {
+  /// Length of the hexadecimal digest
+  pub fn hex_len(&self) -> usize {
+    match self {
+      Algorithm::Sha256 | Algorithm::Blake3 => 64,
+      Algorithm::Sha512 => 128,
+    }
+  }
+
+  /// Hashes the file at `path` without loading it into memory
+  pub fn hash_file<P: AsRef<Path>>(&self, path: P) -> io::Result<String> {
+    let reader = BufReader::new(File::open(path)?);
+    match self {
+      Algorithm::Sha256 => super::sha256_reader(reader),
+      Algorithm::Sha512 => super::sha512_reader(reader),
+      Algorithm::Blake3 => super::blake3_reader(reader),
+    }
+  }
+}
+
+/// Differences between a directory tree and a manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+  /// Files present in the tree but not in the manifest
+  pub added: Vec<String>,
+  /// Files listed in the manifest but absent from the tree
+  pub missing: Vec<String>,
+  /// Files whose digest differs from the manifest
+  pub modified: Vec<String>,
+}
+
+impl Report {
+  /// Returns `true` when the tree matches the manifest exactly
+  pub fn is_clean(&self) -> bool {
+    self.added.is_empty() && self.missing.is_empty() && self.modified.is_empty()
+  }
+}
+
+/// A set of relative paths and their digests
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use helpers::hash::manifest::{Algorithm, Manifest};
+///
+/// let manifest = Manifest::generate("backup", Algorithm::Blake3).unwrap();
+/// manifest.save("backup.b3sum").unwrap();
+///
+/// let manifest = Manifest::load("backup.b3sum", Algorithm::Blake3).unwrap();
+/// let report = manifest.verify("backup").unwrap();
+/// println!("modified: {:?}", report.modified);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+  algorithm: Algorithm,
+  entries: BTreeMap<String, String>,
+}
+
+impl Manifest {
+  /// Walks `root` and hashes every regular file below it
+  ///
+  /// Symbolic links and special files are skipped.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the tree cannot be read or contains a non UTF-8 path
+  pub fn generate<P: AsRef<Path>>(root: P, algorithm: Algorithm) -> io::Result<Self> {
+    let root = root.as_ref();
+    let files = list_files(root)?;
+    let entries = hash_files(root, files, algorithm)?.into_iter().collect();
+    Ok(Self { algorithm, entries })
+  }
+
+  /// Parses the text of a manifest written by this module or a `*sum` tool
+  ///
+  /// # Errors
+  ///
+  /// Returns `io::ErrorKind::InvalidData` for malformed lines or digests whose
+  /// length does not match `algorithm`
+  pub fn parse(text: &str, algorithm: Algorithm) -> io::Result<Self> {
+    let mut entries = BTreeMap::new();
+    for (number, line) in text.lines().enumerate() {
+      if line.is_empty() {
+        continue;
+      }
+      let invalid = || {
+        io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!("invalid manifest line {}", number + 1),
+        )
+      };
+      let (escaped, line) = match line.strip_prefix('\\') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+      };
+      let (digest, rest) = line.split_once(' ').ok_or_else(invalid)?;
+      let path = rest
+        .strip_prefix(' ')
+        .or_else(|| rest.strip_prefix('*'))
+        .ok_or_else(invalid)?;
+      if digest.len() != algorithm.hex_len() || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+      }
+      let path = if escaped {
+        unescape(path).ok_or_else(invalid)?
+      } else {
+        path.to_string()
+      };
+      entries.insert(path, digest.to_ascii_lowercase());
+    }
+    Ok(Self { algorithm, entries })
+  }
+
+  /// Reads and parses a manifest file
+  pub fn load<P: AsRef<Path>>(path: P, algorithm: Algorithm) -> io::Result<Self> {
+    Self::parse(&fs::read_to_string(path)?, algorithm)
+  }
+
+  /// Writes the manifest to a file
+  pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    self.write_to(&mut file)?;
+    file.flush()
+  }
+
+  /// Writes the manifest lines to `writer`
+  pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+    write!(writer, "{self}")
+  }
+
+  /// Algorithm of the digests in this manifest
+  pub fn algorithm(&self) -> Algorithm {
+    self.algorithm
+  }
+
+  /// Relative paths and their digests, ordered by path
+  pub fn entries(&self) -> &BTreeMap<String, String> {
+    &self.entries
+  }
+
+  /// Compares the tree under `root` with this manifest
+  ///
+  /// Only files present on both sides are hashed, in parallel.
+  pub fn verify<P: AsRef<Path>>(&self, root: P) -> io::Result<Report> {
+    let root = root.as_ref();
+    let files = list_files(root)?;
+    let mut report = Report::default();
+    let mut common = Vec::new();
+    for file in &files {
+      if self.entries.contains_key(file) {
+        common.push(file.clone());
+      } else {
+        report.added.push(file.clone());
+      }
+    }
+    report.missing = self
+      .entries
+      .keys()
+      .filter(|path| files.binary_search(path).is_err())
+      .cloned()
+      .collect();
+    report.modified = hash_files(root, common, self.algorithm)?
+      .into_iter()
+      .filter(|(path, digest)| self.entries[path] != *digest)
+      .map(|(path, _)| path)
+      .collect();
+    Ok(report)
+  }
+}
+
+impl fmt::Display for Manifest {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (path, digest) in &self.entries {
+      if path.contains(['\\', '\n', '\r']) {
+        writeln!(f, "\\{digest}  {}", escape(path))?;
+      } else {
+        writeln!(f, "{digest}  {path}")?;
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Escapes a path the way coreutils does for names containing `\` or newlines
+fn escape(path: &str) -> String {
+  path
+    .replace('\\', "\\\\")
+    .replace('\n', "\\n")
+    .replace('\r', "\\r")
+}
+
+fn unescape(path: &str) -> Option<String> {
+  let mut out = String::with_capacity(path.len());
+  let mut chars = path.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      out.push(c);
+      continue;
+    }
+    match chars.next()? {
+      '\\' => out.push('\\'),
+      'n' => out.push('\n'),
+      'r' => out.push('\r'),
+      _ => return None,
+    }
+  }
+  Some(out)
+}
+
+/// Lists regular files below `root` as sorted `/`-separated relative paths
+fn list_files(root: &Path) -> io::Result<Vec<String>> {
+  let mut files = Vec::new();
+  let mut dirs = vec![root.to_path_buf()];
+  while let Some(dir) = dirs.pop() {
+    for entry in fs::read_dir(&dir)? {
+      let entry = entry?;
+      let file_type = entry.file_type()?;
+      if file_type.is_dir() {
+        dirs.push(entry.path());
+      } else if file_type.is_file() {
+        let path = entry.path();
+        let relative = path.strip_prefix(root).expect("entry is below root");
+        let parts = relative
+          .components()
+          .map(|c| {
+            c.as_os_str().to_str().ok_or_else(|| {
+              io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("non UTF-8 path: {}", path.display()),
+              )
+            })
+          })
+          .collect::<io::Result<Vec<_>>>()?;
+        files.push(parts.join("/"));
+      }
+    }
+  }
+  files.sort();
+  Ok(files)
+}
+
+fn hash_files(
+  root: &Path,
+  files: Vec<String>,
+  algorithm: Algorithm,
+) -> io::Result<Vec<(String, String)>> {
+  files
+    .into_par_iter()
+    .map(|file| {
+      let digest = algorithm.hash_file(root.join(&file))?;
+      Ok((file, digest))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::hash::manifest::{Algorithm, Manifest};
+  use crate::hash::{blake3, sha256};
+  use std::fs;
+  use std::path::PathBuf;
+
+  fn temp_tree(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("helpers-manifest-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("sub/deeper")).unwrap();
+    fs::write(root.join("a.txt"), "alpha").unwrap();
+    fs::write(root.join("sub/b.txt"), "beta").unwrap();
+    fs::write(root.join("sub/deeper/c.bin"), [0u8, 1, 2, 3]).unwrap();
+    root
+  }
+
+  #[test]
+  fn test_generate_format() {
+    let root = temp_tree("format");
+    let manifest = Manifest::generate(&root, Algorithm::Sha256).unwrap();
+    let expected = format!(
+      "{}  a.txt\n{}  sub/b.txt\n{}  sub/deeper/c.bin\n",
+      sha256(b"alpha"),
+      sha256(b"beta"),
+      sha256(&[0, 1, 2, 3])
+    );
+    assert_eq!(manifest.to_string(), expected);
+    let parsed = Manifest::parse(&expected, Algorithm::Sha256).unwrap();
+    assert_eq!(parsed, manifest);
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn test_verify() {
+    let root = temp_tree("verify");
+    let manifest = Manifest::generate(&root, Algorithm::Blake3).unwrap();
+    assert_eq!(manifest.entries()["a.txt"], blake3(b"alpha"));
+    assert!(manifest.verify(&root).unwrap().is_clean());
+
+    fs::write(root.join("a.txt"), "changed").unwrap();
+    fs::remove_file(root.join("sub/b.txt")).unwrap();
+    fs::write(root.join("sub/new.txt"), "new").unwrap();
+    let report = manifest.verify(&root).unwrap();
+    assert_eq!(report.modified, vec!["a.txt"]);
+    assert_eq!(report.missing, vec!["sub/b.txt"]);
+    assert_eq!(report.added, vec!["sub/new.txt"]);
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn test_parse() {
+    let digest = "0".repeat(64);
+    let text = format!("{digest} *binary.bin\n\\{digest}  back\\\\slash\\nnewline\n");
+    let manifest = Manifest::parse(&text, Algorithm::Sha256).unwrap();
+    assert!(manifest.entries().contains_key("binary.bin"));
+    assert!(manifest.entries().contains_key("back\\slash\nnewline"));
+    assert!(manifest.to_string().contains("\\\\slash\\n"));
+    assert!(Manifest::parse(&text, Algorithm::Sha512).is_err());
+    assert!(Manifest::parse("nonsense\n", Algorithm::Sha256).is_err());
+  }
+}
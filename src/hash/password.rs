@@ -0,0 +1,619 @@
+//! Password strength estimation and breached-password lookup
+//!
+//! Meant to run before [`bcrypt`](super::bcrypt) or [`argon2`](super::argon2), so weak
+//! or leaked passwords are rejected instead of being hashed and stored.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Passwords that are rejected outright, whatever their length
+const COMMON_PASSWORDS: [&str; 40] = [
+  "123456",
+  "password",
+  "12345678",
+  "qwerty",
+  "123456789",
+  "12345",
+  "1234",
+  "111111",
+  "1234567",
+  "dragon",
+  "123123",
+  "baseball",
+  "abc123",
+  "football",
+  "monkey",
+  "letmein",
+  "696969",
+  "shadow",
+  "master",
+  "666666",
+  "qwertyuiop",
+  "123321",
+  "mustang",
+  "1234567890",
+  "michael",
+  "654321",
+  "superman",
+  "1qaz2wsx",
+  "7777777",
+  "121212",
+  "000000",
+  "qazwsx",
+  "123qwe",
+  "killer",
+  "trustno1",
+  "jordan",
+  "jennifer",
+  "zxcvbnm",
+  "asdfgh",
+  "iloveyou",
+];
+
+/// Words that make a password easy to guess when they appear inside it
+const DICTIONARY: [&str; 63] = [
+  "password",
+  "passwd",
+  "admin",
+  "administrator",
+  "root",
+  "user",
+  "login",
+  "welcome",
+  "letmein",
+  "dragon",
+  "monkey",
+  "master",
+  "shadow",
+  "sunshine",
+  "princess",
+  "football",
+  "baseball",
+  "soccer",
+  "hockey",
+  "batman",
+  "superman",
+  "trustno",
+  "iloveyou",
+  "love",
+  "secret",
+  "hello",
+  "freedom",
+  "whatever",
+  "qwerty",
+  "charlie",
+  "michael",
+  "jordan",
+  "jennifer",
+  "hunter",
+  "ranger",
+  "buster",
+  "thomas",
+  "robert",
+  "tigger",
+  "summer",
+  "winter",
+  "spring",
+  "autumn",
+  "starwars",
+  "computer",
+  "internet",
+  "server",
+  "access",
+  "default",
+  "guest",
+  "test",
+  "changeme",
+  "pass",
+  "god",
+  "angel",
+  "cookie",
+  "cheese",
+  "pepper",
+  "ginger",
+  "orange",
+  "banana",
+  "apple",
+  "flower",
+];
+
+/// Rows of a QWERTY keyboard, used to detect keyboard walks such as `asdf`
+const KEYBOARD_ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Minimum length below which a password is always considered weak
+pub const MIN_LENGTH: usize = 8;
+
+/// Number of leading characters [`strength`] analyses
+///
+/// Pattern detection is quadratic in the length, and the password is
+/// untrusted input; 256 characters are far more than a score of 4 needs.
+const ANALYZED_LEN: usize = 256;
+
+/// A hint explaining why a password received its score
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Feedback {
+  /// The password is shorter than [`MIN_LENGTH`]
+  TooShort,
+  /// The password is one of the most commonly used passwords
+  CommonPassword,
+  /// The password contains a dictionary word, possibly with leet substitutions
+  DictionaryWord(String),
+  /// The password contains an alphabetical, numerical or keyboard sequence
+  Sequence(String),
+  /// The password contains repeated characters or a repeated block
+  Repeat(String),
+  /// The password uses fewer than three character classes
+  FewCharacterClasses,
+}
+
+impl fmt::Display for Feedback {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Feedback::TooShort => write!(f, "use at least {MIN_LENGTH} characters"),
+      Feedback::CommonPassword => write!(f, "this is a very common password"),
+      Feedback::DictionaryWord(word) => write!(f, "avoid dictionary words such as \"{word}\""),
+      Feedback::Sequence(seq) => write!(f, "avoid sequences such as \"{seq}\""),
+      Feedback::Repeat(rep) => write!(f, "avoid repeated characters such as \"{rep}\""),
+      Feedback::FewCharacterClasses => {
+        write!(f, "mix lowercase, uppercase, digits and symbols")
+      }
+    }
+  }
+}
+
+/// Result of [`strength`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strength {
+  /// Score from 0 (very weak) to 4 (very strong)
+  pub score: u8,
+  /// Estimated entropy in bits, after discounting detected patterns
+  pub entropy: f64,
+  /// Reasons for the score, empty for strong passwords
+  pub feedback: Vec<Feedback>,
+}
+
+impl Strength {
+  /// Returns `true` when the score is at least `min_score`
+  pub fn is_at_least(&self, min_score: u8) -> bool {
+    self.score >= min_score
+  }
+}
+
+/// Estimates the strength of a password
+///
+/// The estimate starts from the brute-force entropy of the character pool and
+/// discounts dictionary words, sequences and repeats found in the password.
+/// Only the first 256 characters are analysed, so very long input cannot
+/// make the check expensive.
+///
+/// # Parameters
+///
+/// * `password` - The password to evaluate
+///
+/// # Returns
+///
+/// A [`Strength`] with a 0-4 score, the estimated entropy and feedback
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::password::strength;
+///
+/// assert_eq!(strength("password1").score, 0);
+/// assert!(strength("k7#Vq9!zPw2$mR").score >= 3);
+/// ```
+pub fn strength(password: &str) -> Strength {
+  let end = password
+    .char_indices()
+    .nth(ANALYZED_LEN)
+    .map_or(password.len(), |(i, _)| i);
+  let password = &password[..end];
+  let chars: Vec<char> = password.chars().collect();
+  let lower: Vec<char> = password.to_lowercase().chars().collect();
+  // `to_lowercase` may change the length for some non-ASCII characters, in which
+  // case the positions no longer line up and the original characters are used.
+  let lower = if lower.len() == chars.len() {
+    lower
+  } else {
+    chars.clone()
+  };
+  let unleet: Vec<char> = lower.iter().map(|&c| unleet(c)).collect();
+  let pool = pool_size(&chars);
+  let bits_per_char = (pool as f64).log2();
+
+  let mut feedback = Vec::new();
+  let mut covered = vec![false; chars.len()];
+  let mut pattern_bits = 0.0;
+
+  let lower_str: String = lower.iter().collect();
+  let unleet_str: String = unleet.iter().collect();
+  if COMMON_PASSWORDS.contains(&lower_str.as_str())
+    || COMMON_PASSWORDS.contains(&unleet_str.as_str())
+    || COMMON_PASSWORDS.contains(&lower_str.trim_end_matches(|c: char| c.is_ascii_digit()))
+  {
+    return Strength {
+      score: 0,
+      entropy: (COMMON_PASSWORDS.len() as f64).log2(),
+      feedback: vec![Feedback::CommonPassword],
+    };
+  }
+
+  let mut words = DICTIONARY.to_vec();
+  words.sort_by_key(|w| std::cmp::Reverse(w.len()));
+  for word in words {
+    let word: Vec<char> = word.chars().collect();
+    for candidate in [&lower, &unleet] {
+      for start in find_all(candidate, &word) {
+        let span = start..start + word.len();
+        if covered[span.clone()].iter().any(|&c| c) {
+          continue;
+        }
+        covered[span].iter_mut().for_each(|c| *c = true);
+        pattern_bits += (DICTIONARY.len() as f64).log2() + 1.0;
+        push_unique(
+          &mut feedback,
+          Feedback::DictionaryWord(word.iter().collect()),
+        );
+      }
+    }
+  }
+
+  for (start, len) in sequences(&lower) {
+    let span = start..start + len;
+    if covered[span.clone()].iter().any(|&c| c) {
+      continue;
+    }
+    covered[span.clone()].iter_mut().for_each(|c| *c = true);
+    pattern_bits += bits_per_char + (len as f64).log2();
+    push_unique(
+      &mut feedback,
+      Feedback::Sequence(chars[span].iter().collect()),
+    );
+  }
+
+  for (start, len, block) in repeats(&chars) {
+    let span = start..start + len;
+    if covered[span.clone()].iter().any(|&c| c) {
+      continue;
+    }
+    covered[span.clone()].iter_mut().for_each(|c| *c = true);
+    pattern_bits += block as f64 * bits_per_char + ((len / block) as f64).log2();
+    push_unique(
+      &mut feedback,
+      Feedback::Repeat(chars[span].iter().collect()),
+    );
+  }
+
+  let uncovered = covered.iter().filter(|&&c| !c).count();
+  let entropy = uncovered as f64 * bits_per_char + pattern_bits;
+  let mut score = match entropy {
+    e if e < 28.0 => 0,
+    e if e < 36.0 => 1,
+    e if e < 60.0 => 2,
+    e if e < 80.0 => 3,
+    _ => 4,
+  };
+  if chars.len() < MIN_LENGTH {
+    score = score.min(1);
+    feedback.insert(0, Feedback::TooShort);
+  }
+  if score < 3 && character_classes(&chars) < 3 {
+    feedback.push(Feedback::FewCharacterClasses);
+  }
+  Strength {
+    score,
+    entropy,
+    feedback,
+  }
+}
+
+fn unleet(c: char) -> char {
+  match c {
+    '0' => 'o',
+    '1' | '!' => 'i',
+    '3' => 'e',
+    '4' | '@' => 'a',
+    '5' | '$' => 's',
+    '7' => 't',
+    _ => c,
+  }
+}
+
+fn pool_size(chars: &[char]) -> u32 {
+  let mut pool = 0;
+  if chars.iter().any(|c| c.is_ascii_lowercase()) {
+    pool += 26;
+  }
+  if chars.iter().any(|c| c.is_ascii_uppercase()) {
+    pool += 26;
+  }
+  if chars.iter().any(|c| c.is_ascii_digit()) {
+    pool += 10;
+  }
+  if chars.iter().any(|c| c.is_ascii_punctuation() || *c == ' ') {
+    pool += 33;
+  }
+  if chars.iter().any(|c| !c.is_ascii()) {
+    pool += 100;
+  }
+  pool.max(1)
+}
+
+fn character_classes(chars: &[char]) -> usize {
+  [
+    chars.iter().any(|c| c.is_ascii_lowercase()),
+    chars.iter().any(|c| c.is_ascii_uppercase()),
+    chars.iter().any(|c| c.is_ascii_digit()),
+    chars.iter().any(|c| !c.is_ascii_alphanumeric()),
+  ]
+  .iter()
+  .filter(|&&present| present)
+  .count()
+}
+
+fn push_unique(feedback: &mut Vec<Feedback>, item: Feedback) {
+  if !feedback.contains(&item) {
+    feedback.push(item);
+  }
+}
+
+fn find_all(haystack: &[char], needle: &[char]) -> Vec<usize> {
+  if needle.is_empty() || needle.len() > haystack.len() {
+    return Vec::new();
+  }
+  (0..=haystack.len() - needle.len())
+    .filter(|&i| &haystack[i..i + needle.len()] == needle)
+    .collect()
+}
+
+/// Finds runs of at least three characters that step by +1/-1 or follow a keyboard row
+fn sequences(chars: &[char]) -> Vec<(usize, usize)> {
+  let follows = |a: char, b: char| -> Option<i32> {
+    if a.is_ascii_alphanumeric() && b.is_ascii_alphanumeric() {
+      let step = b as i32 - a as i32;
+      if step == 1 || step == -1 {
+        return Some(step);
+      }
+    }
+    KEYBOARD_ROWS.iter().find_map(|row| {
+      let pa = row.find(a)?;
+      let pb = row.find(b)?;
+      match pb as i32 - pa as i32 {
+        1 => Some(2),
+        -1 => Some(-2),
+        _ => None,
+      }
+    })
+  };
+  let mut found = Vec::new();
+  let mut i = 0;
+  while i + 2 < chars.len() {
+    let Some(step) = follows(chars[i], chars[i + 1]) else {
+      i += 1;
+      continue;
+    };
+    let mut j = i + 1;
+    while j + 1 < chars.len() && follows(chars[j], chars[j + 1]) == Some(step) {
+      j += 1;
+    }
+    if j - i + 1 >= 3 {
+      found.push((i, j - i + 1));
+      i = j + 1;
+    } else {
+      i += 1;
+    }
+  }
+  found
+}
+
+/// Finds repeated blocks, returning `(start, total length, block length)`
+fn repeats(chars: &[char]) -> Vec<(usize, usize, usize)> {
+  let mut found = Vec::new();
+  let mut i = 0;
+  while i < chars.len() {
+    let mut best: Option<(usize, usize)> = None;
+    for block in 1..=(chars.len() - i) / 2 {
+      let mut count = 1;
+      while i + (count + 1) * block <= chars.len()
+        && chars[i..i + block] == chars[i + count * block..i + (count + 1) * block]
+      {
+        count += 1;
+      }
+      let total = count * block;
+      let long_enough = if block == 1 { count >= 3 } else { count >= 2 };
+      if long_enough && best.is_none_or(|(len, _)| total > len) {
+        best = Some((total, block));
+      }
+    }
+    match best {
+      Some((total, block)) => {
+        found.push((i, total, block));
+        i += total;
+      }
+      None => i += 1,
+    }
+  }
+  found
+}
+
+/// Looks up passwords in a local copy of the "Have I Been Pwned" password list
+///
+/// The file must use the SHA-1 format of the downloadable list ordered by hash:
+/// one `HASH:COUNT` entry per line, with 40 uppercase hexadecimal characters.
+/// Lookups do a binary search with seeks, so the file is never loaded into memory.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use helpers::hash::password::BreachedPasswords;
+///
+/// let mut list = BreachedPasswords::open("pwned-passwords-sha1-ordered-by-hash.txt").unwrap();
+/// if list.is_breached("hunter2").unwrap() {
+///   println!("Pick another password");
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BreachedPasswords<R = File> {
+  reader: BufReader<R>,
+  len: u64,
+}
+
+impl BreachedPasswords<File> {
+  /// Opens a sorted HIBP-format file
+  pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+    Self::new(File::open(path)?)
+  }
+}
+
+impl<R: Read + Seek> BreachedPasswords<R> {
+  /// Wraps any seekable source of sorted `HASH:COUNT` lines
+  pub fn new(mut reader: R) -> io::Result<Self> {
+    let len = reader.seek(SeekFrom::End(0))?;
+    Ok(Self {
+      reader: BufReader::new(reader),
+      len,
+    })
+  }
+
+  /// Returns how many times the password appears in breaches, if at all
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(Some(count))` - The password is in the list
+  /// * `Ok(None)` - The password is not in the list
+  /// * `Err(io::Error)` - The file could not be read
+  pub fn count(&mut self, password: &str) -> io::Result<Option<u64>> {
+    self.count_hash(&super::sha1(password.as_bytes()))
+  }
+
+  /// Returns `true` if the password appears in the list
+  pub fn is_breached(&mut self, password: &str) -> io::Result<bool> {
+    Ok(self.count(password)?.is_some())
+  }
+
+  /// Same as [`count`](Self::count), for an already computed SHA-1 hex digest
+  pub fn count_hash(&mut self, sha1_hex: &str) -> io::Result<Option<u64>> {
+    let target = sha1_hex.to_ascii_uppercase();
+    let (mut lo, mut hi) = (0, self.len);
+    // Invariant: a matching line, if any, starts in `lo..hi`.
+    while lo < hi {
+      let mid = lo + (hi - lo) / 2;
+      let Some((start, next, line)) = self.line_at(mid)? else {
+        hi = mid;
+        continue;
+      };
+      if start >= hi {
+        hi = mid;
+        continue;
+      }
+      let (hash, count) = line.split_once(':').unwrap_or((line.as_str(), ""));
+      match hash.to_ascii_uppercase().as_str().cmp(target.as_str()) {
+        Ordering::Equal => return Ok(Some(count.trim().parse().unwrap_or(0))),
+        Ordering::Less => lo = next,
+        Ordering::Greater => hi = mid,
+      }
+    }
+    Ok(None)
+  }
+
+  /// Reads the first line starting at or after `pos`, returning its start offset,
+  /// the offset of the following line and its trimmed content
+  fn line_at(&mut self, pos: u64) -> io::Result<Option<(u64, u64, String)>> {
+    let mut start = pos;
+    if pos > 0 {
+      self.reader.seek(SeekFrom::Start(pos - 1))?;
+      let mut skipped = Vec::new();
+      start = pos - 1 + self.reader.read_until(b'\n', &mut skipped)? as u64;
+    } else {
+      self.reader.seek(SeekFrom::Start(0))?;
+    }
+    let mut line = String::new();
+    let read = self.reader.read_line(&mut line)? as u64;
+    if read == 0 {
+      return Ok(None);
+    }
+    let content = line.trim_end().to_string();
+    Ok(Some((start, start + read, content)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::hash::password::{strength, BreachedPasswords, Feedback};
+  use crate::hash::sha1;
+  use std::io::Cursor;
+
+  #[test]
+  fn test_strength_common() {
+    let result = strength("password");
+    assert_eq!(result.score, 0);
+    assert_eq!(result.feedback, vec![Feedback::CommonPassword]);
+    assert_eq!(strength("P@ssw0rd").score, 0);
+  }
+
+  #[test]
+  fn test_strength_patterns() {
+    let result = strength("abcdefgh1234");
+    assert!(result.score <= 1);
+    assert!(matches!(result.feedback[0], Feedback::Sequence(_)));
+
+    let result = strength("zzzzzzzzzzzz");
+    assert_eq!(result.score, 0);
+    assert_eq!(
+      result.feedback[0],
+      Feedback::Repeat("zzzzzzzzzzzz".to_string())
+    );
+
+    let result = strength("Sunshine2024!");
+    assert!(result
+      .feedback
+      .contains(&Feedback::DictionaryWord("sunshine".to_string())));
+
+    let result = strength("asdfghjk");
+    assert!(matches!(result.feedback[0], Feedback::Sequence(_)));
+  }
+
+  #[test]
+  fn test_strength_strong() {
+    let result = strength("k7#Vq9!zPw2$mR");
+    assert!(result.score >= 3, "{result:?}");
+    assert!(strength("correct horse battery staple").score >= 3);
+  }
+
+  #[test]
+  fn test_strength_long() {
+    // Only the start is analysed, which is still recognized as a repeat
+    let result = strength(&"ab".repeat(100_000));
+    assert_eq!(result.score, 0);
+    assert!(matches!(result.feedback[0], Feedback::Repeat(_)));
+  }
+
+  #[test]
+  fn test_strength_short() {
+    let result = strength("x9#Qa");
+    assert!(result.score <= 1);
+    assert_eq!(result.feedback[0], Feedback::TooShort);
+  }
+
+  #[test]
+  fn test_breached() {
+    let mut lines: Vec<String> = ["123456", "password", "hunter2", "qwerty", "letmein"]
+      .iter()
+      .enumerate()
+      .map(|(i, p)| format!("{}:{}\r\n", sha1(p.as_bytes()).to_uppercase(), i + 1))
+      .collect();
+    lines.sort();
+    let mut list = BreachedPasswords::new(Cursor::new(lines.concat())).unwrap();
+    assert_eq!(list.count("123456").unwrap(), Some(1));
+    assert_eq!(list.count("letmein").unwrap(), Some(5));
+    assert!(list.is_breached("hunter2").unwrap());
+    assert!(list.is_breached("qwerty").unwrap());
+    assert!(list.is_breached("password").unwrap());
+    assert!(!list.is_breached("not in the list").unwrap());
+    let mut empty = BreachedPasswords::new(Cursor::new(Vec::new())).unwrap();
+    assert!(!empty.is_breached("password").unwrap());
+  }
+}
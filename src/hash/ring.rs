@@ -0,0 +1,408 @@
+//! Key-to-node routing with consistent hashing and rendezvous hashing
+//!
+//! Both schemes move only about `1 / n` of the keys when a node joins or leaves,
+//! and both hash with [`xxh64`](super::xxh64).
+
+use std::collections::{BTreeMap, HashSet};
+
+use super::xxh64;
+
+/// Default number of virtual nodes per unit of weight
+pub const DEFAULT_REPLICAS: usize = 160;
+
+/// A consistent-hash ring with virtual nodes and weighted members
+///
+/// Every member is placed on the ring `replicas * weight` times; a key belongs
+/// to the first virtual node found clockwise from its hash.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::ring::HashRing;
+///
+/// let mut ring = HashRing::new();
+/// ring.add("cache-a");
+/// ring.add("cache-b");
+/// ring.add_weighted("cache-c", 2);
+/// let node = ring.get("user:42").unwrap();
+/// println!("user:42 lives on {node}");
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashRing<N> {
+  replicas: usize,
+  points: BTreeMap<u64, N>,
+  members: Vec<(N, u32)>,
+}
+
+impl<N> Default for HashRing<N>
+where
+  N: AsRef<[u8]> + Clone + PartialEq,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<N> HashRing<N>
+where
+  N: AsRef<[u8]> + Clone + PartialEq,
+{
+  /// Creates an empty ring with [`DEFAULT_REPLICAS`] virtual nodes per weight unit
+  pub fn new() -> Self {
+    Self::with_replicas(DEFAULT_REPLICAS)
+  }
+
+  /// Creates an empty ring with a custom number of virtual nodes per weight unit
+  ///
+  /// More virtual nodes give a better balance at the cost of memory.
+  pub fn with_replicas(replicas: usize) -> Self {
+    Self {
+      replicas: replicas.max(1),
+      points: BTreeMap::new(),
+      members: Vec::new(),
+    }
+  }
+
+  /// Adds a member with weight 1
+  pub fn add(&mut self, node: N) {
+    self.add_weighted(node, 1);
+  }
+
+  /// Adds a member, or updates its weight if it is already on the ring
+  ///
+  /// A member with weight 2 receives about twice as many keys as one with weight 1.
+  /// A weight of 0 removes the member.
+  pub fn add_weighted(&mut self, node: N, weight: u32) {
+    self.remove(&node);
+    if weight == 0 {
+      return;
+    }
+    for i in 0..self.replicas * weight as usize {
+      let point = virtual_point(node.as_ref(), i);
+      match self.points.get(&point) {
+        // On the rare collision the smallest name wins, so the layout does not
+        // depend on insertion order.
+        Some(existing) if existing.as_ref() <= node.as_ref() => {}
+        _ => {
+          self.points.insert(point, node.clone());
+        }
+      }
+    }
+    self.members.push((node, weight));
+  }
+
+  /// Removes a member, returning `true` if it was on the ring
+  pub fn remove(&mut self, node: &N) -> bool {
+    let Some(index) = self.members.iter().position(|(n, _)| n == node) else {
+      return false;
+    };
+    let (node, weight) = self.members.remove(index);
+    let mut freed = HashSet::new();
+    for i in 0..self.replicas * weight as usize {
+      let point = virtual_point(node.as_ref(), i);
+      if self.points.get(&point) == Some(&node) {
+        self.points.remove(&point);
+        freed.insert(point);
+      }
+    }
+    // Give each freed point back to another member that also hashed there, in
+    // a single pass over the remaining virtual nodes.
+    if !freed.is_empty() {
+      for (other, w) in &self.members {
+        for i in 0..self.replicas * *w as usize {
+          let point = virtual_point(other.as_ref(), i);
+          if !freed.contains(&point) {
+            continue;
+          }
+          match self.points.get(&point) {
+            Some(existing) if existing.as_ref() <= other.as_ref() => {}
+            _ => {
+              self.points.insert(point, other.clone());
+            }
+          }
+        }
+      }
+    }
+    true
+  }
+
+  /// Returns the member responsible for `key`, or `None` if the ring is empty
+  pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&N> {
+    let hash = xxh64(key.as_ref(), 0);
+    self
+      .points
+      .range(hash..)
+      .next()
+      .or_else(|| self.points.iter().next())
+      .map(|(_, node)| node)
+  }
+
+  /// Returns up to `n` distinct members for `key`, in ring order
+  ///
+  /// The first one is the same as [`get`](Self::get); the others are natural
+  /// replica locations.
+  pub fn get_n<K: AsRef<[u8]>>(&self, key: K, n: usize) -> Vec<&N> {
+    let hash = xxh64(key.as_ref(), 0);
+    let mut found: Vec<&N> = Vec::with_capacity(n.min(self.members.len()));
+    for (_, node) in self.points.range(hash..).chain(self.points.range(..hash)) {
+      if found.len() >= n.min(self.members.len()) {
+        break;
+      }
+      if !found.contains(&node) {
+        found.push(node);
+      }
+    }
+    found
+  }
+
+  /// Members and their weights, in insertion order
+  pub fn members(&self) -> impl Iterator<Item = (&N, u32)> {
+    self.members.iter().map(|(n, w)| (n, *w))
+  }
+
+  /// Number of members
+  pub fn len(&self) -> usize {
+    self.members.len()
+  }
+
+  /// Returns `true` if the ring has no members
+  pub fn is_empty(&self) -> bool {
+    self.members.is_empty()
+  }
+}
+
+fn virtual_point(node: &[u8], index: usize) -> u64 {
+  xxh64(node, index as u64)
+}
+
+/// Rendezvous (highest random weight) hashing
+///
+/// Each key goes to the member with the highest score for that key. It needs no
+/// ring and moves the minimum number of keys, but a lookup is `O(n)`.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::ring::Rendezvous;
+///
+/// let mut nodes = Rendezvous::new();
+/// nodes.add("shard-1");
+/// nodes.add("shard-2");
+/// nodes.add_weighted("shard-3", 3.0);
+/// let shard = nodes.get("order:1001").unwrap();
+/// println!("order:1001 lives on {shard}");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rendezvous<N> {
+  members: Vec<(N, f64)>,
+}
+
+impl<N> Default for Rendezvous<N>
+where
+  N: AsRef<[u8]> + PartialEq,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<N> Rendezvous<N>
+where
+  N: AsRef<[u8]> + PartialEq,
+{
+  /// Creates an empty set of members
+  pub fn new() -> Self {
+    Self {
+      members: Vec::new(),
+    }
+  }
+
+  /// Adds a member with weight 1.0
+  pub fn add(&mut self, node: N) {
+    self.add_weighted(node, 1.0);
+  }
+
+  /// Adds a member, or updates its weight if already present
+  ///
+  /// Non-positive weights remove the member.
+  pub fn add_weighted(&mut self, node: N, weight: f64) {
+    self.remove(&node);
+    if weight > 0.0 {
+      self.members.push((node, weight));
+    }
+  }
+
+  /// Removes a member, returning `true` if it was present
+  pub fn remove(&mut self, node: &N) -> bool {
+    let before = self.members.len();
+    self.members.retain(|(n, _)| n != node);
+    self.members.len() != before
+  }
+
+  /// Returns the member with the highest score for `key`
+  pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&N> {
+    let key = key.as_ref();
+    self
+      .members
+      .iter()
+      .map(|(node, weight)| (score(node.as_ref(), *weight, key), node))
+      .max_by(|a, b| a.0.total_cmp(&b.0))
+      .map(|(_, node)| node)
+  }
+
+  /// Returns up to `n` members ordered by decreasing score for `key`
+  pub fn get_n<K: AsRef<[u8]>>(&self, key: K, n: usize) -> Vec<&N> {
+    let key = key.as_ref();
+    let mut scored: Vec<_> = self
+      .members
+      .iter()
+      .map(|(node, weight)| (score(node.as_ref(), *weight, key), node))
+      .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().take(n).map(|(_, node)| node).collect()
+  }
+
+  /// Members and their weights, in insertion order
+  pub fn members(&self) -> impl Iterator<Item = (&N, f64)> {
+    self.members.iter().map(|(n, w)| (n, *w))
+  }
+
+  /// Number of members
+  pub fn len(&self) -> usize {
+    self.members.len()
+  }
+
+  /// Returns `true` if there are no members
+  pub fn is_empty(&self) -> bool {
+    self.members.is_empty()
+  }
+}
+
+/// Weighted rendezvous score, `-weight / ln(u)` with `u` uniform in `(0, 1)`
+fn score(node: &[u8], weight: f64, key: &[u8]) -> f64 {
+  let hash = xxh64(key, xxh64(node, 0));
+  let unit = ((hash >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+  -weight / unit.ln()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::hash::ring::{HashRing, Rendezvous};
+  use std::collections::HashMap;
+
+  const KEYS: usize = 50_000;
+
+  fn nodes(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("node-{i}")).collect()
+  }
+
+  fn counts<'a>(assign: impl Fn(&str) -> &'a String) -> HashMap<&'a String, usize> {
+    let mut counts = HashMap::new();
+    for i in 0..KEYS {
+      *counts.entry(assign(&format!("key-{i}"))).or_insert(0) += 1;
+    }
+    counts
+  }
+
+  #[test]
+  fn test_ring_balance() {
+    let mut ring = HashRing::new();
+    for node in nodes(10) {
+      ring.add(node);
+    }
+    let counts = counts(|k| ring.get(k).unwrap());
+    assert_eq!(counts.len(), 10);
+    for count in counts.values() {
+      let share = *count as f64 / KEYS as f64;
+      assert!((0.07..0.13).contains(&share), "share {share}");
+    }
+  }
+
+  #[test]
+  fn test_ring_weighted() {
+    let mut ring = HashRing::new();
+    ring.add("light".to_string());
+    ring.add_weighted("heavy".to_string(), 3);
+    let counts = counts(|k| ring.get(k).unwrap());
+    let heavy = counts[&"heavy".to_string()] as f64 / KEYS as f64;
+    assert!((0.68..0.82).contains(&heavy), "heavy share {heavy}");
+  }
+
+  #[test]
+  fn test_ring_minimal_movement() {
+    let mut ring = HashRing::new();
+    for node in nodes(10) {
+      ring.add(node);
+    }
+    let keys: Vec<String> = (0..KEYS).map(|i| format!("key-{i}")).collect();
+    let before: Vec<String> = keys.iter().map(|k| ring.get(k).unwrap().clone()).collect();
+
+    ring.add("node-new".to_string());
+    let mut moved = 0;
+    for (key, old) in keys.iter().zip(&before) {
+      let new = ring.get(key).unwrap();
+      if new != old {
+        assert_eq!(new, "node-new");
+        moved += 1;
+      }
+    }
+    let share = moved as f64 / KEYS as f64;
+    assert!((0.06..0.12).contains(&share), "moved {share}");
+
+    assert!(ring.remove(&"node-new".to_string()));
+    assert!(!ring.remove(&"node-new".to_string()));
+    for (key, old) in keys.iter().zip(&before) {
+      assert_eq!(ring.get(key).unwrap(), old);
+    }
+  }
+
+  #[test]
+  fn test_ring_get_n() {
+    let mut ring = HashRing::with_replicas(50);
+    assert!(ring.get("key").is_none());
+    for node in nodes(5) {
+      ring.add(node);
+    }
+    let replicas = ring.get_n("key", 3);
+    assert_eq!(replicas.len(), 3);
+    assert_eq!(replicas[0], ring.get("key").unwrap());
+    assert!(replicas[0] != replicas[1] && replicas[1] != replicas[2]);
+    assert_eq!(ring.get_n("key", 10).len(), 5);
+  }
+
+  #[test]
+  fn test_rendezvous() {
+    let mut hrw = Rendezvous::new();
+    assert!(hrw.get("key").is_none());
+    for node in nodes(10) {
+      hrw.add(node);
+    }
+    let counts_before = counts(|k| hrw.get(k).unwrap());
+    for count in counts_before.values() {
+      let share = *count as f64 / KEYS as f64;
+      assert!((0.08..0.12).contains(&share), "share {share}");
+    }
+
+    let keys: Vec<String> = (0..KEYS).map(|i| format!("key-{i}")).collect();
+    let before: Vec<String> = keys.iter().map(|k| hrw.get(k).unwrap().clone()).collect();
+    hrw.remove(&"node-3".to_string());
+    for (key, old) in keys.iter().zip(&before) {
+      if old != "node-3" {
+        assert_eq!(hrw.get(key).unwrap(), old);
+      }
+    }
+    let top = hrw.get_n("key-1", 3);
+    assert_eq!(top[0], hrw.get("key-1").unwrap());
+    assert_eq!(top.len(), 3);
+  }
+
+  #[test]
+  fn test_rendezvous_weighted() {
+    let mut hrw = Rendezvous::new();
+    hrw.add("light".to_string());
+    hrw.add_weighted("heavy".to_string(), 3.0);
+    let counts = counts(|k| hrw.get(k).unwrap());
+    let heavy = counts[&"heavy".to_string()] as f64 / KEYS as f64;
+    assert!((0.72..0.78).contains(&heavy), "heavy share {heavy}");
+  }
+}
@@ -0,0 +1,291 @@
+//! Locality-sensitive hashes for near-duplicate detection
+//!
+//! Unlike [`md5`](super::md5) or [`blake3`](super::blake3), similar inputs produce
+//! similar signatures: SimHash fingerprints differ in few bits and MinHash
+//! signatures share many slots when the inputs have many shingles in common.
+
+use std::collections::HashSet;
+
+/// How text is split into shingles before hashing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shingle {
+  /// Overlapping sequences of `n` characters, whitespace collapsed
+  Chars(usize),
+  /// Overlapping sequences of `n` words
+  Words(usize),
+}
+
+impl Default for Shingle {
+  fn default() -> Self {
+    Shingle::Words(3)
+  }
+}
+
+impl Shingle {
+  /// Splits `text` into lowercase shingles
+  ///
+  /// Text shorter than one shingle yields a single shingle with the whole text.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use helpers::hash::similarity::Shingle;
+  ///
+  /// let shingles = Shingle::Words(2).split("The quick brown fox");
+  /// assert_eq!(shingles, vec!["the quick", "quick brown", "brown fox"]);
+  /// ```
+  pub fn split(&self, text: &str) -> Vec<String> {
+    let text = text.to_lowercase();
+    match *self {
+      Shingle::Chars(n) => {
+        let chars: Vec<char> = text
+          .split_whitespace()
+          .collect::<Vec<_>>()
+          .join(" ")
+          .chars()
+          .collect();
+        windows(&chars, n.max(1), |w| w.iter().collect())
+      }
+      Shingle::Words(n) => {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        windows(&words, n.max(1), |w| w.join(" "))
+      }
+    }
+  }
+}
+
+fn windows<T>(items: &[T], n: usize, join: impl Fn(&[T]) -> String) -> Vec<String> {
+  if items.is_empty() {
+    return Vec::new();
+  }
+  if items.len() <= n {
+    return vec![join(items)];
+  }
+  items.windows(n).map(join).collect()
+}
+
+/// 64-bit hash of a shingle, keyed by `seed`
+fn hash64(data: &[u8], seed: u64) -> u64 {
+  let mut hasher = blake3::Hasher::new_keyed(&seed_key(seed));
+  hasher.update(data);
+  let bytes = hasher.finalize();
+  u64::from_le_bytes(
+    bytes.as_bytes()[..8]
+      .try_into()
+      .expect("digest is 32 bytes"),
+  )
+}
+
+/// splitmix64 finalizer, a bijection on `u64`
+fn mix64(mut z: u64) -> u64 {
+  z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+  z ^ (z >> 31)
+}
+
+fn seed_key(seed: u64) -> [u8; 32] {
+  let mut key = [0u8; 32];
+  key[..8].copy_from_slice(&seed.to_le_bytes());
+  key
+}
+
+/// Computes the 64-bit SimHash fingerprint of `text`
+///
+/// # Parameters
+///
+/// * `text` - The text to fingerprint
+/// * `shingle` - How the text is split into features, each with weight one
+///
+/// # Returns
+///
+/// A fingerprint whose [`hamming_distance`] to another one approximates how
+/// different the two texts are
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::similarity::{hamming_distance, simhash, Shingle};
+///
+/// let a = simhash("the quick brown fox jumps over the lazy dog", Shingle::Chars(4));
+/// let b = simhash("the quick brown fox jumped over the lazy dog", Shingle::Chars(4));
+/// let c = simhash("completely unrelated sentence about cooking pasta", Shingle::Chars(4));
+/// assert!(hamming_distance(a, b) < hamming_distance(a, c));
+/// ```
+pub fn simhash(text: &str, shingle: Shingle) -> u64 {
+  simhash_weighted(shingle.split(text).iter().map(|s| (s.as_bytes(), 1.0)))
+}
+
+/// Computes a SimHash fingerprint from already extracted, weighted features
+pub fn simhash_weighted<'a, I>(features: I) -> u64
+where
+  I: IntoIterator<Item = (&'a [u8], f64)>,
+{
+  let mut counts = [0f64; 64];
+  for (feature, weight) in features {
+    let hash = hash64(feature, 0);
+    for (bit, count) in counts.iter_mut().enumerate() {
+      if hash >> bit & 1 == 1 {
+        *count += weight;
+      } else {
+        *count -= weight;
+      }
+    }
+  }
+  counts
+    .iter()
+    .enumerate()
+    .filter(|(_, &count)| count > 0.0)
+    .fold(0, |acc, (bit, _)| acc | 1 << bit)
+}
+
+/// Number of differing bits between two fingerprints
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+  (a ^ b).count_ones()
+}
+
+/// Similarity between two SimHash fingerprints, from 0.0 to 1.0
+pub fn simhash_similarity(a: u64, b: u64) -> f64 {
+  1.0 - hamming_distance(a, b) as f64 / 64.0
+}
+
+/// MinHash signature generator
+///
+/// Signatures are only comparable when produced by hashers with the same number
+/// of permutations, seed and shingling.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::hash::similarity::{MinHasher, Shingle};
+///
+/// let hasher = MinHasher::new(128, Shingle::Words(2));
+/// let a = hasher.signature("the cat sat on the mat with the hat");
+/// let b = hasher.signature("the cat sat on the mat with a hat");
+/// let estimate = MinHasher::jaccard(&a, &b);
+/// assert!(estimate > 0.4 && estimate < 0.95);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinHasher {
+  seeds: Vec<u64>,
+  shingle: Shingle,
+}
+
+impl MinHasher {
+  /// Creates a hasher with `permutations` hash functions and the default seed
+  ///
+  /// The standard error of the Jaccard estimate is about `1 / sqrt(permutations)`.
+  pub fn new(permutations: usize, shingle: Shingle) -> Self {
+    Self::with_seed(permutations, shingle, 0)
+  }
+
+  /// Creates a hasher whose hash functions are derived from `seed`
+  pub fn with_seed(permutations: usize, shingle: Shingle, seed: u64) -> Self {
+    let seeds = (0..permutations as u64)
+      .map(|i| seed.wrapping_mul(0x9e37_79b9_7f4a_7c15).wrapping_add(i + 1))
+      .collect();
+    Self { seeds, shingle }
+  }
+
+  /// Number of values in a signature
+  pub fn permutations(&self) -> usize {
+    self.seeds.len()
+  }
+
+  /// Computes the signature of `text`
+  pub fn signature(&self, text: &str) -> Vec<u64> {
+    let shingles: HashSet<String> = self.shingle.split(text).into_iter().collect();
+    self.signature_of(shingles.iter().map(|s| s.as_bytes()))
+  }
+
+  /// Computes the signature of an already shingled set
+  pub fn signature_of<'a, I>(&self, items: I) -> Vec<u64>
+  where
+    I: IntoIterator<Item = &'a [u8]>,
+  {
+    let mut signature = vec![u64::MAX; self.seeds.len()];
+    for item in items {
+      // One cryptographic hash per item, then a cheap mixing step per slot.
+      let base = hash64(item, 0);
+      for (slot, &seed) in signature.iter_mut().zip(&self.seeds) {
+        *slot = (*slot).min(mix64(base ^ seed));
+      }
+    }
+    signature
+  }
+
+  /// Estimates the Jaccard similarity of the sets behind two signatures
+  ///
+  /// # Panics
+  ///
+  /// Panics if the signatures have different lengths
+  pub fn jaccard(a: &[u64], b: &[u64]) -> f64 {
+    assert_eq!(a.len(), b.len(), "signatures must have the same length");
+    if a.is_empty() {
+      return 0.0;
+    }
+    let equal = a.iter().zip(b).filter(|(x, y)| x == y).count();
+    equal as f64 / a.len() as f64
+  }
+}
+
+/// Exact Jaccard similarity of the shingle sets of two texts
+///
+/// Useful to check a MinHash estimate, or when the texts are small enough to
+/// compare directly.
+pub fn jaccard(a: &str, b: &str, shingle: Shingle) -> f64 {
+  let a: HashSet<String> = shingle.split(a).into_iter().collect();
+  let b: HashSet<String> = shingle.split(b).into_iter().collect();
+  let union = a.union(&b).count();
+  if union == 0 {
+    return 0.0;
+  }
+  a.intersection(&b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::hash::similarity::{
+    hamming_distance, jaccard, simhash, simhash_similarity, MinHasher, Shingle,
+  };
+
+  #[test]
+  fn test_shingle() {
+    assert_eq!(Shingle::Chars(3).split("AbCd"), vec!["abc", "bcd"]);
+    assert_eq!(Shingle::Chars(3).split("ab"), vec!["ab"]);
+    assert_eq!(Shingle::Words(2).split("  one   two  "), vec!["one two"]);
+    assert!(Shingle::Words(2).split("").is_empty());
+  }
+
+  #[test]
+  fn test_simhash() {
+    let text = "near duplicate detection with locality sensitive hashing";
+    assert_eq!(
+      simhash(text, Shingle::Chars(3)),
+      simhash(text, Shingle::Chars(3))
+    );
+    assert_eq!(hamming_distance(0b1011, 0b0110), 3);
+    assert_eq!(simhash_similarity(7, 7), 1.0);
+
+    let near = "near duplicate detection with locality-sensitive hashing!";
+    let far = "a recipe for tomato soup with basil and garlic";
+    let a = simhash(text, Shingle::Chars(3));
+    assert!(hamming_distance(a, simhash(near, Shingle::Chars(3))) <= 10);
+    assert!(hamming_distance(a, simhash(far, Shingle::Chars(3))) >= 15);
+  }
+
+  #[test]
+  fn test_minhash() {
+    let hasher = MinHasher::new(256, Shingle::Chars(3));
+    let a = "minhash estimates the jaccard similarity of two sets";
+    let b = "minhash estimates the jaccard similarity between two sets";
+    let exact = jaccard(a, b, Shingle::Chars(3));
+    let estimate = MinHasher::jaccard(&hasher.signature(a), &hasher.signature(b));
+    assert!((exact - estimate).abs() < 0.15, "{exact} vs {estimate}");
+    assert_eq!(
+      MinHasher::jaccard(&hasher.signature(a), &hasher.signature(a)),
+      1.0
+    );
+    let other = MinHasher::with_seed(256, Shingle::Chars(3), 42);
+    assert_ne!(hasher.signature(a), other.signature(a));
+  }
+}
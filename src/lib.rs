@@ -9,6 +9,11 @@
 //! - jwt
 //! - hash
 //! - time
+//! - derive: `#[derive(Random)]`, enables rand
+
+// Lets the derive macros refer to `::helpers` from within this crate's own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as helpers;
 
 #[cfg(feature = "jwt")]
 pub mod jwt;
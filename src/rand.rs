@@ -1,7 +1,30 @@
 use rand::{
+  distr::{Bernoulli, Distribution},
   seq::{IndexedRandom, SliceRandom},
-  Rng,
+  Rng as _, RngCore, SeedableRng,
 };
+use rand_chacha::ChaCha12Rng;
+
+pub use rand::distr::uniform::Error as RangeError;
+pub use rand::distr::uniform::{SampleRange, SampleUniform};
+pub use rand::distr::BernoulliError;
+
+pub mod backoff;
+pub mod color;
+pub mod dist;
+pub mod fake;
+pub mod geo;
+pub mod password;
+pub mod permutation;
+pub mod random;
+pub mod sample;
+pub mod secure;
+pub mod sim;
+
+/// Derives [`Random`] for structs and enums, see the [`random`] module
+#[cfg(feature = "derive")]
+pub use helpers_derive::Random;
+pub use random::{Random, RandomLen};
 
 /// Generates a random integer within the specified range (inclusive).
 ///
@@ -22,6 +45,10 @@ use rand::{
 /// let random_number = min_max(1, 10);
 /// println!("{}", random_number); // Prints a random number between 1 and 10 (inclusive)
 /// ```
+///
+/// # Panics
+///
+/// Panics if `min` is greater than `max`, use [`range`] for a non-panicking version
 pub fn min_max(min: i32, max: i32) -> i32 {
   rand::rng().random_range(min..=max)
 }
@@ -46,12 +73,46 @@ pub fn min_max(min: i32, max: i32) -> i32 {
 ///
 /// # Panics
 ///
-/// - Panics if `min` is greater than `max`
+/// - Panics if `min` is greater than `max` or either is not finite, use [`range`]
+///   for a non-panicking version
 /// - Uses thread-local random number generator
 pub fn min_max_float(min: f64, max: f64) -> f64 {
   rand::rng().random_range(min..=max)
 }
 
+/// Generate a random number within a half-open or inclusive range
+///
+/// Works with every primitive integer and float type, unlike [`min_max`] and
+/// [`min_max_float`] which only accept `i32` and `f64` and panic on bad input.
+///
+/// # Parameters
+///
+/// - `range`: Either `low..high` (high excluded) or `low..=high` (high included)
+///
+/// # Returns
+///
+/// - `Ok(value)` within the range
+/// - `Err(RangeError::EmptyRange)` if the range contains no value, e.g. `5..5` or `3..=1`
+/// - `Err(RangeError::NonFinite)` if a float bound is infinite or NaN
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::{range, RangeError};
+///
+/// let byte: u8 = range(0..=255).unwrap();
+/// let ratio = range(0.0f32..1.0).unwrap();
+/// assert!((0.0..1.0).contains(&ratio));
+/// assert_eq!(range(10u64..10), Err(RangeError::EmptyRange));
+/// ```
+pub fn range<T, R>(range: R) -> Result<T, RangeError>
+where
+  T: SampleUniform,
+  R: SampleRange<T>,
+{
+  range.sample_single(&mut rand::rng())
+}
+
 /// Generate a random boolean value
 ///
 /// # Returns
@@ -73,6 +134,143 @@ pub fn random_bool() -> bool {
   rand::rng().random_bool(0.5)
 }
 
+/// Generate a random boolean that is `true` with probability `p`
+///
+/// # Parameters
+///
+/// - `p`: Probability of `true`, from 0.0 to 1.0
+///
+/// # Returns
+///
+/// - `Ok(bool)`, always `false` for `p == 0.0` and always `true` for `p == 1.0`
+/// - `Err(BernoulliError::InvalidProbability)` if `p` is outside `0.0..=1.0` or NaN
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::chance;
+///
+/// if chance(0.01).unwrap() {
+///     println!("sampled this request for tracing");
+/// }
+/// assert!(chance(1.5).is_err());
+/// ```
+pub fn chance(p: f64) -> Result<bool, BernoulliError> {
+  chance_with(&mut rand::rng(), p)
+}
+
+fn chance_with<R: RngCore + ?Sized>(rng: &mut R, p: f64) -> Result<bool, BernoulliError> {
+  Ok(Bernoulli::new(p)?.sample(rng))
+}
+
+/// Generate a random boolean that is `true` with probability `numerator / denominator`
+///
+/// Exact: compares a uniform integer below `denominator` with `numerator`
+/// instead of going through a floating-point probability.
+///
+/// # Returns
+///
+/// - `Ok(bool)`
+/// - `Err(BernoulliError::InvalidProbability)` if `denominator` is zero or
+///   smaller than `numerator`
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::chance_ratio;
+///
+/// let critical_hit = chance_ratio(1, 6).unwrap();
+/// assert!(chance_ratio(3, 2).is_err());
+/// ```
+pub fn chance_ratio(numerator: u32, denominator: u32) -> Result<bool, BernoulliError> {
+  chance_ratio_with(&mut rand::rng(), numerator, denominator)
+}
+
+fn chance_ratio_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  numerator: u32,
+  denominator: u32,
+) -> Result<bool, BernoulliError> {
+  if denominator == 0 || numerator > denominator {
+    return Err(BernoulliError::InvalidProbability);
+  }
+  Ok(rng.random_range(0..denominator) < numerator)
+}
+
+/// Count the successes among `n` independent trials with success probability `p`
+///
+/// Draws from the binomial distribution directly, so the cost does not grow
+/// with `n`.
+///
+/// # Returns
+///
+/// - `Ok(count)` between 0 and `n`
+/// - `Err(BernoulliError::InvalidProbability)` if `p` is outside `0.0..=1.0` or NaN
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::bernoulli_trials;
+///
+/// // How many of 1000 packets get dropped at a 2% loss rate?
+/// let dropped = bernoulli_trials(1000, 0.02).unwrap();
+/// assert!(dropped <= 1000);
+/// ```
+pub fn bernoulli_trials(n: u64, p: f64) -> Result<u64, BernoulliError> {
+  bernoulli_trials_with(&mut rand::rng(), n, p)
+}
+
+fn bernoulli_trials_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  n: u64,
+  p: f64,
+) -> Result<u64, BernoulliError> {
+  Bernoulli::new(p)?;
+  Ok(dist::binomial_with(rng, n, p).expect("probability checked above"))
+}
+
+/// Decide deterministically whether `key` falls within a percentage rollout
+///
+/// The key is hashed into one of 2^64 buckets, so the answer for a given key
+/// never changes between calls, processes or machines. Raising `percent` only
+/// adds keys: everything enabled at 10% stays enabled at 20%.
+///
+/// Prefix the key with the flag name (e.g. `"new-checkout:user-42"`) so that
+/// different flags do not all select the same users.
+///
+/// # Parameters
+///
+/// - `key`: A stable identifier such as a user or tenant id
+/// - `percent`: Share of keys to enable, from 0.0 to 100.0; values outside are clamped
+///   and NaN enables nothing
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::rollout;
+///
+/// let enabled = rollout("new-checkout:user-42", 25.0);
+/// assert_eq!(enabled, rollout("new-checkout:user-42", 25.0));
+/// assert!(!rollout("new-checkout:user-42", 0.0));
+/// assert!(rollout("new-checkout:user-42", 100.0));
+/// ```
+pub fn rollout(key: impl AsRef<[u8]>, percent: f64) -> bool {
+  if percent.is_nan() || percent <= 0.0 {
+    return false;
+  }
+  if percent >= 100.0 {
+    return true;
+  }
+  let digest = blake3::hash(key.as_ref());
+  let bucket = u64::from_le_bytes(
+    digest.as_bytes()[..8]
+      .try_into()
+      .expect("digest is 32 bytes"),
+  );
+  // 53 bits are all an f64 can hold exactly, which is plenty of resolution.
+  ((bucket >> 11) as f64 / (1u64 << 53) as f64) < percent / 100.0
+}
+
 /// Randomly select an element from a slice
 ///
 /// # Type Parameters
@@ -132,9 +330,296 @@ pub fn shuffle<T>(slice: &mut [T]) {
   slice.shuffle(&mut rand::rng())
 }
 
+/// A random number generator handle that can be seeded for reproducible results
+///
+/// The free functions of this module use the thread-local generator and give
+/// different results on every run. An `Rng` built with [`Rng::seeded`] always
+/// produces the same sequence for the same seed, which makes tests and
+/// simulations repeatable. It is backed by ChaCha12 and implements
+/// [`rand::RngCore`], so it can be passed to any API expecting a generator.
+///
+/// Its output is predictable from the seed, so it must not be used for keys,
+/// tokens or salts; use [`secure`] for those.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::Rng;
+///
+/// let mut a = Rng::seeded(42);
+/// let mut b = Rng::seeded(42);
+/// assert_eq!(a.min_max(1, 100), b.min_max(1, 100));
+///
+/// let mut cards = vec![1, 2, 3, 4, 5];
+/// a.shuffle(&mut cards);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng(ChaCha12Rng);
+
+impl Rng {
+  /// Creates a generator whose output is fully determined by `seed`
+  pub fn seeded(seed: u64) -> Self {
+    Self(ChaCha12Rng::seed_from_u64(seed))
+  }
+
+  /// Creates a generator seeded from the operating system's entropy source
+  pub fn from_entropy() -> Self {
+    Self(ChaCha12Rng::from_os_rng())
+  }
+
+  /// Creates an independent generator seeded from this one
+  ///
+  /// Useful to hand out reproducible generators to sub-tasks.
+  pub fn fork(&mut self) -> Self {
+    Self(ChaCha12Rng::from_rng(&mut self.0))
+  }
+
+  /// Generates a random integer within the specified range (inclusive)
+  ///
+  /// See [`min_max`]
+  pub fn min_max(&mut self, min: i32, max: i32) -> i32 {
+    self.0.random_range(min..=max)
+  }
+
+  /// Generates a random floating-point number within the specified range (inclusive)
+  ///
+  /// See [`min_max_float`]
+  pub fn min_max_float(&mut self, min: f64, max: f64) -> f64 {
+    self.0.random_range(min..=max)
+  }
+
+  /// Generates a random number within a half-open or inclusive range
+  ///
+  /// See [`range`]
+  pub fn range<T, R>(&mut self, range: R) -> Result<T, RangeError>
+  where
+    T: SampleUniform,
+    R: SampleRange<T>,
+  {
+    range.sample_single(&mut self.0)
+  }
+
+  /// Generates a random boolean value
+  ///
+  /// See [`random_bool`]
+  pub fn random_bool(&mut self) -> bool {
+    self.0.random_bool(0.5)
+  }
+
+  /// Generates a random boolean that is `true` with probability `p`
+  ///
+  /// See [`chance`]
+  pub fn chance(&mut self, p: f64) -> Result<bool, BernoulliError> {
+    chance_with(&mut self.0, p)
+  }
+
+  /// Generates a random boolean that is `true` with probability `numerator / denominator`
+  ///
+  /// See [`chance_ratio`]
+  pub fn chance_ratio(&mut self, numerator: u32, denominator: u32) -> Result<bool, BernoulliError> {
+    chance_ratio_with(&mut self.0, numerator, denominator)
+  }
+
+  /// Counts the successes among `n` independent trials
+  ///
+  /// See [`bernoulli_trials`]
+  pub fn bernoulli_trials(&mut self, n: u64, p: f64) -> Result<u64, BernoulliError> {
+    bernoulli_trials_with(&mut self.0, n, p)
+  }
+
+  /// Randomly selects an element from a slice
+  ///
+  /// See [`random_choice`]
+  pub fn random_choice<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+    slice.choose(&mut self.0)
+  }
+
+  /// Randomly shuffles the elements of a mutable slice in-place
+  ///
+  /// See [`shuffle`]
+  pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+    slice.shuffle(&mut self.0)
+  }
+
+  /// Moves `k` randomly chosen elements to the end of the slice
+  ///
+  /// See [`permutation::partial_shuffle`]
+  pub fn partial_shuffle<'a, T>(&mut self, slice: &'a mut [T], k: usize) -> &'a mut [T] {
+    permutation::partial_shuffle_with(&mut self.0, slice, k)
+  }
+
+  /// Generates a random permutation of `0..n` where no index stays in place
+  ///
+  /// See [`permutation::derangement`]
+  pub fn derangement(&mut self, n: usize) -> Option<Vec<usize>> {
+    permutation::derangement_with(&mut self.0, n)
+  }
+
+  /// Randomly selects an element with probability proportional to its weight
+  ///
+  /// See [`sample::weighted_choice`]
+  pub fn weighted_choice<'a, T>(
+    &mut self,
+    items: &'a [T],
+    weights: &[f64],
+  ) -> Result<&'a T, sample::WeightError> {
+    sample::weighted_choice_with(&mut self.0, items, weights)
+  }
+
+  /// Randomly selects `k` distinct elements of a slice
+  ///
+  /// See [`sample::sample`]
+  pub fn sample<'a, T>(&mut self, slice: &'a [T], k: usize) -> Vec<&'a T> {
+    sample::sample_with(&mut self.0, slice, k)
+  }
+
+  /// Selects `k` elements uniformly from an iterator of unknown length
+  ///
+  /// See [`sample::reservoir`]
+  pub fn reservoir<I: IntoIterator>(&mut self, iter: I, k: usize) -> Vec<I::Item> {
+    sample::reservoir_with(&mut self.0, iter, k)
+  }
+
+  /// Selects one element uniformly from an iterator, in a single pass
+  ///
+  /// See [`sample::choose_iter`]
+  pub fn choose_iter<I: IntoIterator>(&mut self, iter: I) -> Option<I::Item> {
+    sample::choose_iter_with(&mut self.0, iter)
+  }
+
+  /// Randomly selects a key of a map, with probability proportional to its value
+  ///
+  /// See [`sample::choose_weighted_map`]
+  pub fn choose_weighted_map<'a, K, S>(
+    &mut self,
+    map: &'a std::collections::HashMap<K, f64, S>,
+  ) -> Result<&'a K, sample::WeightError> {
+    sample::choose_weighted_map_with(&mut self.0, map)
+  }
+
+  /// Generates a point uniformly within a rectangle
+  ///
+  /// See [`geo::point_in_rect`]
+  pub fn point_in_rect<X, Y>(&mut self, x: X, y: Y) -> Result<[f64; 2], RangeError>
+  where
+    X: SampleRange<f64>,
+    Y: SampleRange<f64>,
+  {
+    geo::point_in_rect_with(&mut self.0, x, y)
+  }
+
+  /// Generates a point uniformly within a circle
+  ///
+  /// See [`geo::point_in_circle`]
+  pub fn point_in_circle(&mut self, center: [f64; 2], radius: f64) -> Result<[f64; 2], RangeError> {
+    geo::point_in_circle_with(&mut self.0, center, radius)
+  }
+
+  /// Generates a point uniformly on the surface of a sphere
+  ///
+  /// See [`geo::point_on_sphere`]
+  pub fn point_on_sphere(&mut self, center: [f64; 3], radius: f64) -> Result<[f64; 3], RangeError> {
+    geo::point_on_sphere_with(&mut self.0, center, radius)
+  }
+
+  /// Generates a vector of length one pointing in a uniformly random direction
+  ///
+  /// See [`geo::unit_vector`]
+  pub fn unit_vector<const N: usize>(&mut self) -> [f64; N] {
+    geo::unit_vector_with(&mut self.0)
+  }
+
+  /// Generates a color within the bounds of a palette
+  ///
+  /// See [`color::Palette`]
+  pub fn color(&mut self, palette: &color::Palette) -> Result<color::Color, RangeError> {
+    palette.color_with(&mut self.0)
+  }
+
+  /// Draws from a normal distribution
+  ///
+  /// See [`dist::normal`]
+  pub fn normal(&mut self, mean: f64, std_dev: f64) -> Result<f64, dist::NormalError> {
+    dist::normal_with(&mut self.0, mean, std_dev)
+  }
+
+  /// Draws from a log-normal distribution
+  ///
+  /// See [`dist::log_normal`]
+  pub fn log_normal(&mut self, mu: f64, sigma: f64) -> Result<f64, dist::NormalError> {
+    dist::log_normal_with(&mut self.0, mu, sigma)
+  }
+
+  /// Draws from an exponential distribution
+  ///
+  /// See [`dist::exponential`]
+  pub fn exponential(&mut self, lambda: f64) -> Result<f64, dist::ExpError> {
+    dist::exponential_with(&mut self.0, lambda)
+  }
+
+  /// Draws from a Poisson distribution
+  ///
+  /// See [`dist::poisson`]
+  pub fn poisson(&mut self, lambda: f64) -> Result<u64, dist::PoissonError> {
+    dist::poisson_with(&mut self.0, lambda)
+  }
+
+  /// Draws from a binomial distribution
+  ///
+  /// See [`dist::binomial`]
+  pub fn binomial(&mut self, n: u64, p: f64) -> Result<u64, dist::BinomialError> {
+    dist::binomial_with(&mut self.0, n, p)
+  }
+
+  /// Draws from a Zipf distribution
+  ///
+  /// See [`dist::zipf`]
+  pub fn zipf(&mut self, n: u64, s: f64) -> Result<u64, dist::ZipfError> {
+    dist::zipf_with(&mut self.0, n, s)
+  }
+
+  /// Draws from a Pareto distribution
+  ///
+  /// See [`dist::pareto`]
+  pub fn pareto(&mut self, scale: f64, shape: f64) -> Result<f64, dist::ParetoError> {
+    dist::pareto_with(&mut self.0, scale, shape)
+  }
+}
+
+impl Default for Rng {
+  fn default() -> Self {
+    Self::from_entropy()
+  }
+}
+
+impl RngCore for Rng {
+  fn next_u32(&mut self) -> u32 {
+    self.0.next_u32()
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    self.0.next_u64()
+  }
+
+  fn fill_bytes(&mut self, dst: &mut [u8]) {
+    self.0.fill_bytes(dst)
+  }
+}
+
+impl SeedableRng for Rng {
+  type Seed = <ChaCha12Rng as SeedableRng>::Seed;
+
+  fn from_seed(seed: Self::Seed) -> Self {
+    Self(ChaCha12Rng::from_seed(seed))
+  }
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::rand::{min_max, min_max_float, random_bool, random_choice, shuffle};
+  use crate::rand::{
+    bernoulli_trials, chance, chance_ratio, min_max, min_max_float, random_bool, random_choice,
+    range, rollout, shuffle, BernoulliError, RangeError, Rng,
+  };
   #[test]
   fn it_works() {
     println!("{}", min_max(1, 10));
@@ -147,4 +632,114 @@ mod tests {
     shuffle(&mut nums);
     println!("Shuffled array: {:?}", nums);
   }
+
+  #[test]
+  fn test_seeded() {
+    let mut a = Rng::seeded(7);
+    let mut b = Rng::seeded(7);
+    for _ in 0..100 {
+      assert_eq!(a.min_max(-50, 50), b.min_max(-50, 50));
+      assert_eq!(a.min_max_float(0.0, 1.0), b.min_max_float(0.0, 1.0));
+      assert_eq!(a.random_bool(), b.random_bool());
+    }
+    let items = [1, 2, 3, 4, 5, 6, 7, 8];
+    assert_eq!(a.random_choice(&items), b.random_choice(&items));
+    assert_eq!(a.random_choice::<i32>(&[]), None);
+    let (mut x, mut y) = (items, items);
+    a.shuffle(&mut x);
+    b.shuffle(&mut y);
+    assert_eq!(x, y);
+
+    let (mut c, mut d) = (Rng::seeded(1), Rng::seeded(2));
+    let c: Vec<i32> = (0..10).map(|_| c.min_max(0, 1000)).collect();
+    let d: Vec<i32> = (0..10).map(|_| d.min_max(0, 1000)).collect();
+    assert_ne!(c, d);
+  }
+  #[test]
+  fn test_fork() {
+    let mut parent = Rng::seeded(3);
+    let mut child = parent.fork();
+    let mut again = Rng::seeded(3).fork();
+    assert_eq!(child.min_max(0, 1_000_000), again.min_max(0, 1_000_000));
+    assert_ne!(child, parent);
+  }
+  #[test]
+  fn test_range() {
+    for _ in 0..1000 {
+      assert!((3..7).contains(&range(3i8..7).unwrap()));
+      assert!((0..=u16::MAX).contains(&range(0u16..=u16::MAX).unwrap()));
+      assert!((-5..=-5).contains(&range(-5i64..=-5).unwrap()));
+      assert!((1..4).contains(&range(1u128..4).unwrap()));
+      assert!((0..2).contains(&range(0usize..2).unwrap()));
+      assert!((-1.0..1.0).contains(&range(-1.0f32..1.0).unwrap()));
+      assert!((2.5..=2.5).contains(&range(2.5f64..=2.5).unwrap()));
+    }
+    assert_eq!(range(i64::MIN..=i64::MAX).map(|_| ()), Ok(()));
+    assert_eq!(range(5u32..5), Err(RangeError::EmptyRange));
+    let (low, high) = (3i32, 1i32);
+    assert_eq!(range(low..=high), Err(RangeError::EmptyRange));
+    assert_eq!(range(high as f64..0.0), Err(RangeError::EmptyRange));
+    assert!(range(0.0..f64::INFINITY).is_err());
+    assert!(range(f64::NAN..1.0).is_err());
+
+    let (mut a, mut b) = (Rng::seeded(9), Rng::seeded(9));
+    assert_eq!(a.range(0u8..100), b.range(0u8..100));
+    assert_eq!(a.range(high..=-low), Err(RangeError::EmptyRange));
+  }
+
+  #[test]
+  fn test_chance() {
+    for _ in 0..100 {
+      assert_eq!(chance(0.0), Ok(false));
+      assert_eq!(chance(1.0), Ok(true));
+      assert_eq!(chance_ratio(0, 7), Ok(false));
+      assert_eq!(chance_ratio(7, 7), Ok(true));
+    }
+    assert_eq!(chance(-0.1), Err(BernoulliError::InvalidProbability));
+    assert_eq!(chance(f64::NAN), Err(BernoulliError::InvalidProbability));
+    assert_eq!(chance_ratio(1, 0), Err(BernoulliError::InvalidProbability));
+    assert_eq!(chance_ratio(4, 3), Err(BernoulliError::InvalidProbability));
+
+    let mut rng = Rng::seeded(11);
+    let hits = (0..100_000).filter(|_| rng.chance(0.3).unwrap()).count();
+    assert!((29_000..31_000).contains(&hits), "{hits}");
+    let hits = (0..90_000)
+      .filter(|_| rng.chance_ratio(1, 3).unwrap())
+      .count();
+    assert!((29_000..31_000).contains(&hits), "{hits}");
+  }
+
+  #[test]
+  fn test_bernoulli_trials() {
+    assert_eq!(bernoulli_trials(50, 0.0), Ok(0));
+    assert_eq!(bernoulli_trials(50, 1.0), Ok(50));
+    assert_eq!(bernoulli_trials(0, 0.5), Ok(0));
+    assert_eq!(
+      bernoulli_trials(10, 2.0),
+      Err(BernoulliError::InvalidProbability)
+    );
+    let mut rng = Rng::seeded(12);
+    let total: u64 = (0..1_000)
+      .map(|_| rng.bernoulli_trials(100, 0.25).unwrap())
+      .sum();
+    assert!((24_000..26_000).contains(&total), "{total}");
+  }
+
+  #[test]
+  fn test_rollout() {
+    let keys: Vec<String> = (0..20_000).map(|i| format!("flag:user-{i}")).collect();
+    let enabled = |percent: f64| keys.iter().filter(|k| rollout(k, percent)).count();
+    assert_eq!(enabled(0.0), 0);
+    assert_eq!(enabled(f64::NAN), 0);
+    assert_eq!(enabled(100.0), keys.len());
+    assert_eq!(enabled(150.0), keys.len());
+    let quarter = enabled(25.0);
+    assert!((4_600..5_400).contains(&quarter), "{quarter}");
+    for key in &keys {
+      assert_eq!(rollout(key, 10.0), rollout(key, 10.0));
+      if rollout(key, 10.0) {
+        assert!(rollout(key, 20.0));
+      }
+    }
+  }
 }
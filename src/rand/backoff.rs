@@ -0,0 +1,355 @@
+//! Retry delays with exponential backoff and jitter
+//!
+//! The jitter strategies follow "Exponential Backoff And Jitter" from the AWS
+//! Architecture Blog. Randomizing delays spreads out the retries of clients
+//! that failed at the same moment instead of having them hit the recovering
+//! service in lockstep.
+
+use std::thread;
+use std::time::Duration;
+
+use super::Rng;
+
+/// How a delay is randomized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Jitter {
+  /// `min(cap, base * multiplier^n)`, no randomness
+  None,
+  /// Uniform between zero and the exponential delay
+  #[default]
+  Full,
+  /// Half the exponential delay plus a uniform share of the other half
+  Equal,
+  /// Uniform between `base` and three times the previous delay, capped
+  Decorrelated,
+}
+
+/// A retry schedule
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use helpers::rand::backoff::{Backoff, Jitter};
+///
+/// let backoff = Backoff {
+///   base: Duration::from_millis(50),
+///   cap: Duration::from_secs(2),
+///   max_attempts: Some(4),
+///   jitter: Jitter::Equal,
+///   ..Default::default()
+/// };
+/// let delays: Vec<Duration> = backoff.delays().collect();
+/// assert_eq!(delays.len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backoff {
+  /// Delay before the first retry, before jitter
+  pub base: Duration,
+  /// Longest delay ever returned
+  pub cap: Duration,
+  /// Growth of the delay between retries
+  pub multiplier: f64,
+  /// Total number of attempts including the first one, `None` to retry forever
+  pub max_attempts: Option<u32>,
+  /// Randomization strategy
+  pub jitter: Jitter,
+}
+
+impl Default for Backoff {
+  /// 100ms doubling up to 30s, full jitter, 5 attempts
+  fn default() -> Self {
+    Self {
+      base: Duration::from_millis(100),
+      cap: Duration::from_secs(30),
+      multiplier: 2.0,
+      max_attempts: Some(5),
+      jitter: Jitter::Full,
+    }
+  }
+}
+
+impl Backoff {
+  /// Iterates over the delays to wait before each retry
+  ///
+  /// Yields one delay fewer than [`max_attempts`](Self::max_attempts).
+  pub fn delays(&self) -> Delays {
+    self.delays_with(Rng::from_entropy())
+  }
+
+  /// Iterates over the delays with the given generator, for reproducible schedules
+  pub fn delays_with(&self, rng: Rng) -> Delays {
+    Delays {
+      backoff: self.clone(),
+      rng,
+      retry: 0,
+      previous: self.base,
+    }
+  }
+
+  /// Calls `op` until it succeeds or the attempts are exhausted, sleeping between calls
+  ///
+  /// `op` receives the attempt number, starting at zero.
+  ///
+  /// # Returns
+  ///
+  /// The first success, or the last error
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use std::time::Duration;
+  /// use helpers::rand::backoff::Backoff;
+  ///
+  /// let backoff = Backoff { base: Duration::from_millis(1), ..Default::default() };
+  /// let result = backoff.retry(|attempt| if attempt < 2 { Err("busy") } else { Ok(attempt) });
+  /// assert_eq!(result, Ok(2));
+  /// ```
+  pub fn retry<T, E>(&self, op: impl FnMut(u32) -> Result<T, E>) -> Result<T, E> {
+    self.retry_if(op, |_| true)
+  }
+
+  /// Like [`retry`](Self::retry), but gives up at once when `retryable` returns `false`
+  pub fn retry_if<T, E>(
+    &self,
+    op: impl FnMut(u32) -> Result<T, E>,
+    retryable: impl FnMut(&E) -> bool,
+  ) -> Result<T, E> {
+    Self::run(self.delays(), op, retryable)
+  }
+
+  /// Like [`retry`](Self::retry), with delays drawn from the given generator
+  pub fn retry_with<T, E>(&self, rng: Rng, op: impl FnMut(u32) -> Result<T, E>) -> Result<T, E> {
+    self.retry_if_with(rng, op, |_| true)
+  }
+
+  /// Like [`retry_if`](Self::retry_if), with delays drawn from the given generator
+  pub fn retry_if_with<T, E>(
+    &self,
+    rng: Rng,
+    op: impl FnMut(u32) -> Result<T, E>,
+    retryable: impl FnMut(&E) -> bool,
+  ) -> Result<T, E> {
+    Self::run(self.delays_with(rng), op, retryable)
+  }
+
+  fn run<T, E>(
+    mut delays: Delays,
+    mut op: impl FnMut(u32) -> Result<T, E>,
+    mut retryable: impl FnMut(&E) -> bool,
+  ) -> Result<T, E> {
+    let mut attempt = 0;
+    loop {
+      match op(attempt) {
+        Ok(value) => return Ok(value),
+        Err(e) if !retryable(&e) => return Err(e),
+        Err(e) => match delays.next() {
+          Some(delay) => thread::sleep(delay),
+          None => return Err(e),
+        },
+      }
+      attempt += 1;
+    }
+  }
+}
+
+/// Iterator over retry delays, created by [`Backoff::delays`]
+#[derive(Debug, Clone)]
+pub struct Delays {
+  backoff: Backoff,
+  rng: Rng,
+  retry: u32,
+  previous: Duration,
+}
+
+impl Delays {
+  /// `min(cap, base * multiplier^retry)` in seconds
+  fn exponential(&self) -> f64 {
+    let Backoff {
+      base,
+      cap,
+      multiplier,
+      ..
+    } = self.backoff;
+    let delay = base.as_secs_f64() * multiplier.powi(self.retry.min(i32::MAX as u32) as i32);
+    delay.min(cap.as_secs_f64())
+  }
+
+  fn uniform(&mut self, low: f64, high: f64) -> f64 {
+    self.rng.range(low..=high).unwrap_or(low)
+  }
+}
+
+impl Iterator for Delays {
+  type Item = Duration;
+
+  fn next(&mut self) -> Option<Duration> {
+    if let Some(max) = self.backoff.max_attempts {
+      if self.retry.saturating_add(1) >= max {
+        return None;
+      }
+    }
+    let seconds = match self.backoff.jitter {
+      Jitter::None => self.exponential(),
+      Jitter::Full => self.uniform(0.0, self.exponential()),
+      Jitter::Equal => {
+        let half = self.exponential() / 2.0;
+        half + self.uniform(0.0, half)
+      }
+      Jitter::Decorrelated => {
+        let (base, cap) = (
+          self.backoff.base.as_secs_f64(),
+          self.backoff.cap.as_secs_f64(),
+        );
+        let high = (self.previous.as_secs_f64() * 3.0).max(base);
+        self.uniform(base, high).min(cap)
+      }
+    };
+    self.retry += 1;
+    // A cap near `Duration::MAX` does not survive the round trip through f64.
+    let delay = Duration::try_from_secs_f64(seconds.max(0.0)).unwrap_or(self.backoff.cap);
+    self.previous = delay;
+    Some(delay)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::Cell;
+  use std::time::Duration;
+
+  use crate::rand::backoff::{Backoff, Jitter};
+  use crate::rand::Rng;
+
+  fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+  }
+
+  fn schedule(jitter: Jitter) -> Backoff {
+    Backoff {
+      base: ms(100),
+      cap: ms(1_000),
+      multiplier: 2.0,
+      max_attempts: Some(8),
+      jitter,
+    }
+  }
+
+  #[test]
+  fn test_exponential() {
+    let delays: Vec<Duration> = schedule(Jitter::None).delays().collect();
+    assert_eq!(
+      delays,
+      [
+        ms(100),
+        ms(200),
+        ms(400),
+        ms(800),
+        ms(1_000),
+        ms(1_000),
+        ms(1_000)
+      ]
+    );
+    let forever = Backoff {
+      max_attempts: None,
+      ..schedule(Jitter::None)
+    };
+    assert_eq!(forever.delays().nth(10_000), Some(ms(1_000)));
+    let once = Backoff {
+      max_attempts: Some(1),
+      ..schedule(Jitter::None)
+    };
+    assert_eq!(once.delays().next(), None);
+
+    let uncapped = Backoff {
+      cap: Duration::MAX,
+      max_attempts: None,
+      ..schedule(Jitter::None)
+    };
+    assert_eq!(uncapped.delays().nth(200), Some(Duration::MAX));
+    for jitter in [Jitter::Full, Jitter::Equal, Jitter::Decorrelated] {
+      let backoff = Backoff {
+        jitter,
+        ..uncapped.clone()
+      };
+      assert_eq!(backoff.delays().take(200).count(), 200);
+    }
+  }
+
+  #[test]
+  fn test_jitter() {
+    let exponential: Vec<Duration> = schedule(Jitter::None).delays().collect();
+    for _ in 0..200 {
+      let full = schedule(Jitter::Full).delays();
+      let equal = schedule(Jitter::Equal).delays();
+      for ((f, e), x) in full.zip(equal).zip(&exponential) {
+        assert!(f <= *x);
+        assert!(e >= *x / 2 && e <= *x);
+      }
+      let mut previous = ms(100);
+      for d in schedule(Jitter::Decorrelated).delays() {
+        assert!(d >= ms(100) && d <= ms(1_000));
+        assert!(d <= previous * 3);
+        previous = d;
+      }
+    }
+  }
+
+  #[test]
+  fn test_seeded() {
+    let backoff = schedule(Jitter::Decorrelated);
+    let a: Vec<Duration> = backoff.delays_with(Rng::seeded(1)).collect();
+    let b: Vec<Duration> = backoff.delays_with(Rng::seeded(1)).collect();
+    assert_eq!(a, b);
+    assert_ne!(a, backoff.delays_with(Rng::seeded(2)).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn test_retry() {
+    let backoff = Backoff {
+      base: ms(1),
+      cap: ms(2),
+      max_attempts: Some(3),
+      ..Default::default()
+    };
+    let calls = Cell::new(0);
+    let result: Result<(), &str> = backoff.retry(|_| {
+      calls.set(calls.get() + 1);
+      Err("down")
+    });
+    assert_eq!(result, Err("down"));
+    assert_eq!(calls.get(), 3);
+
+    assert_eq!(
+      backoff.retry(|attempt| if attempt == 0 { Err(()) } else { Ok(attempt) }),
+      Ok(1)
+    );
+
+    calls.set(0);
+    let result: Result<(), &str> = backoff.retry_if(
+      |_| {
+        calls.set(calls.get() + 1);
+        Err("bad request")
+      },
+      |e| *e != "bad request",
+    );
+    assert_eq!(result, Err("bad request"));
+    assert_eq!(calls.get(), 1);
+
+    calls.set(0);
+    let result: Result<(), &str> = backoff.retry_with(Rng::seeded(1), |_| {
+      calls.set(calls.get() + 1);
+      Err("down")
+    });
+    assert_eq!(result, Err("down"));
+    assert_eq!(calls.get(), 3);
+    assert_eq!(
+      backoff.retry_if_with(
+        Rng::seeded(1),
+        |a| if a < 2 { Err(()) } else { Ok(a) },
+        |_| true
+      ),
+      Ok(2)
+    );
+  }
+}
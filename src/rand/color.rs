@@ -0,0 +1,240 @@
+//! Random colors within hue, saturation and lightness bounds
+//!
+//! Constraining saturation and lightness rather than picking RGB channels
+//! independently avoids muddy and neon colors, which makes the results usable
+//! for placeholder avatars and chart series.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use rand::distr::{Distribution, Uniform};
+use rand::{Rng as _, RngCore};
+
+use super::{RangeError, SampleRange};
+
+/// A color in the HSL model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+  /// Hue in degrees, in `0..360`
+  pub hue: f64,
+  /// Saturation, in `0..=1`
+  pub saturation: f64,
+  /// Lightness, in `0..=1`
+  pub lightness: f64,
+}
+
+impl Color {
+  /// Converts to 8-bit sRGB channels
+  pub fn to_rgb(&self) -> [u8; 3] {
+    let (s, l) = (
+      self.saturation.clamp(0.0, 1.0),
+      self.lightness.clamp(0.0, 1.0),
+    );
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let sector = self.hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+      0 => (chroma, x, 0.0),
+      1 => (x, chroma, 0.0),
+      2 => (0.0, chroma, x),
+      3 => (0.0, x, chroma),
+      4 => (x, 0.0, chroma),
+      _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+    [r, g, b].map(|c| ((c + m) * 255.0).round() as u8)
+  }
+
+  /// Formats as a CSS hex color such as `#3fa2c8`
+  pub fn to_hex(&self) -> String {
+    self.to_string()
+  }
+}
+
+impl fmt::Display for Color {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let [r, g, b] = self.to_rgb();
+    write!(f, "#{r:02x}{g:02x}{b:02x}")
+  }
+}
+
+/// Bounds of the colors to generate
+///
+/// Hues are in degrees and may extend past 360 to wrap around red, such as
+/// `330.0..=390.0`. Saturation and lightness are clamped to `0..=1`.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::color::Palette;
+///
+/// let avatar = Palette::default().color().unwrap();
+/// println!("background: {avatar}");
+///
+/// let pastels = Palette {
+///   saturation: 0.6..=0.8,
+///   lightness: 0.8..=0.9,
+///   ..Default::default()
+/// };
+/// let series = pastels.colors(5).unwrap();
+/// assert_eq!(series.len(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+  /// Hue in degrees
+  pub hue: RangeInclusive<f64>,
+  /// Saturation, between 0 for gray and 1 for the purest color
+  pub saturation: RangeInclusive<f64>,
+  /// Lightness, between 0 for black and 1 for white
+  pub lightness: RangeInclusive<f64>,
+}
+
+impl Default for Palette {
+  /// Any hue, moderately saturated, neither dark nor washed out
+  fn default() -> Self {
+    Self {
+      hue: 0.0..=360.0,
+      saturation: 0.45..=0.75,
+      lightness: 0.45..=0.65,
+    }
+  }
+}
+
+impl Palette {
+  /// Generates a color with the thread-local generator
+  ///
+  /// # Errors
+  ///
+  /// A `RangeError` if a range is empty or not finite
+  pub fn color(&self) -> Result<Color, RangeError> {
+    self.color_with(&mut rand::rng())
+  }
+
+  /// Generates a color with the given generator
+  pub fn color_with<R: RngCore + ?Sized>(&self, rng: &mut R) -> Result<Color, RangeError> {
+    let hue = self.hue.clone().sample_single(rng)?;
+    let saturation = self.saturation.clone().sample_single(rng)?;
+    let lightness = self.lightness.clone().sample_single(rng)?;
+    Ok(hsl(hue, saturation, lightness))
+  }
+
+  /// Generates `n` colors whose hues are spread out, for telling series apart
+  ///
+  /// Hues advance by the golden ratio of the hue range from a random start, so
+  /// consecutive colors never look alike and any prefix stays well spread.
+  ///
+  /// # Errors
+  ///
+  /// A `RangeError` if a range is empty or not finite
+  pub fn colors(&self, n: usize) -> Result<Vec<Color>, RangeError> {
+    self.colors_with(&mut rand::rng(), n)
+  }
+
+  /// Generates `n` spread out colors with the given generator
+  pub fn colors_with<R: RngCore + ?Sized>(
+    &self,
+    rng: &mut R,
+    n: usize,
+  ) -> Result<Vec<Color>, RangeError> {
+    const GOLDEN: f64 = 0.618_033_988_749_895;
+    // Built up front so that an invalid palette is reported even when `n` is
+    // zero; the hue is only validated since it is spread rather than sampled.
+    Uniform::new_inclusive(*self.hue.start(), *self.hue.end())?;
+    let saturation = Uniform::new_inclusive(*self.saturation.start(), *self.saturation.end())?;
+    let lightness = Uniform::new_inclusive(*self.lightness.start(), *self.lightness.end())?;
+    let (start, end) = (*self.hue.start(), *self.hue.end());
+    let offset = rng.random::<f64>();
+    let colors = (0..n)
+      .map(|i| {
+        let fraction = (offset + i as f64 * GOLDEN).fract();
+        let hue = start + (end - start) * fraction;
+        hsl(hue, saturation.sample(rng), lightness.sample(rng))
+      })
+      .collect();
+    Ok(colors)
+  }
+}
+
+/// Builds a color with the hue wrapped to `0..360` and the rest clamped to `0..=1`
+fn hsl(hue: f64, saturation: f64, lightness: f64) -> Color {
+  // Tiny negative hues round up to exactly 360.
+  let hue = hue.rem_euclid(360.0);
+  Color {
+    hue: if hue < 360.0 { hue } else { 0.0 },
+    saturation: saturation.clamp(0.0, 1.0),
+    lightness: lightness.clamp(0.0, 1.0),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::rand::color::{Color, Palette};
+  use crate::rand::{RangeError, Rng};
+
+  fn hsl(hue: f64, saturation: f64, lightness: f64) -> Color {
+    Color {
+      hue,
+      saturation,
+      lightness,
+    }
+  }
+
+  #[test]
+  fn test_to_rgb() {
+    assert_eq!(hsl(0.0, 1.0, 0.5).to_rgb(), [255, 0, 0]);
+    assert_eq!(hsl(120.0, 1.0, 0.5).to_rgb(), [0, 255, 0]);
+    assert_eq!(hsl(240.0, 1.0, 0.5).to_rgb(), [0, 0, 255]);
+    assert_eq!(hsl(360.0, 1.0, 0.5).to_rgb(), [255, 0, 0]);
+    assert_eq!(hsl(200.0, 0.0, 1.0).to_rgb(), [255, 255, 255]);
+    assert_eq!(hsl(30.0, 0.5, 0.0).to_hex(), "#000000");
+    assert_eq!(hsl(210.0, 0.5, 0.5).to_string(), "#4080bf");
+  }
+
+  #[test]
+  fn test_palette() {
+    let mut rng = Rng::seeded(1);
+    let reds = Palette {
+      hue: 330.0..=390.0,
+      saturation: 0.5..=1.5,
+      lightness: 0.4..=0.6,
+    };
+    for _ in 0..1_000 {
+      let color = rng.color(&reds).unwrap();
+      assert!(color.hue >= 330.0 || color.hue <= 30.0, "{color:?}");
+      assert!((0.5..=1.0).contains(&color.saturation));
+      assert!((0.4..=0.6).contains(&color.lightness));
+    }
+
+    let series = Palette::default().colors_with(&mut rng, 3).unwrap();
+    for (a, b) in series.iter().zip(&series[1..]) {
+      let distance = (a.hue - b.hue).rem_euclid(360.0);
+      assert!((distance.min(360.0 - distance) - 137.5).abs() < 0.1);
+    }
+
+    let broken = Palette {
+      lightness: 0.8..=0.2,
+      ..Default::default()
+    };
+    assert_eq!(broken.color(), Err(RangeError::EmptyRange));
+    assert_eq!(broken.colors(2), Err(RangeError::EmptyRange));
+    assert_eq!(broken.colors(0), Err(RangeError::EmptyRange));
+    let broken = Palette {
+      hue: 0.0..=f64::INFINITY,
+      ..Default::default()
+    };
+    assert_eq!(broken.color(), Err(RangeError::NonFinite));
+    let overflowing = Palette {
+      hue: -f64::MAX..=f64::MAX,
+      ..Default::default()
+    };
+    assert_eq!(overflowing.color(), Err(RangeError::NonFinite));
+    assert_eq!(overflowing.colors(0), Err(RangeError::NonFinite));
+
+    let below_zero = Palette {
+      hue: -1e-20..=-1e-20,
+      ..Default::default()
+    };
+    assert_eq!(below_zero.color().unwrap().hue, 0.0);
+    assert_eq!(below_zero.colors(1).unwrap()[0].hue, 0.0);
+  }
+}
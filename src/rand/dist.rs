@@ -0,0 +1,251 @@
+//! Samplers for common statistical distributions
+//!
+//! Every function draws a single value with the thread-local generator and
+//! validates its parameters instead of panicking. The same samplers are
+//! available as methods of [`Rng`](super::Rng) for reproducible results, and
+//! the re-exported distribution types can be reused for many draws.
+
+use rand::RngCore;
+
+pub use rand_distr::{
+  Binomial, BinomialError, Distribution, Exp, ExpError, LogNormal, Normal, NormalError, Pareto,
+  ParetoError, Poisson, PoissonError, Zipf, ZipfError,
+};
+
+/// Draws from a normal (Gaussian) distribution
+///
+/// # Parameters
+///
+/// - `mean`: Center of the distribution
+/// - `std_dev`: Standard deviation, must be finite
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::dist::normal;
+///
+/// let latency_ms = normal(120.0, 15.0).unwrap();
+/// println!("simulated latency: {latency_ms:.1}ms");
+/// ```
+pub fn normal(mean: f64, std_dev: f64) -> Result<f64, NormalError> {
+  normal_with(&mut rand::rng(), mean, std_dev)
+}
+
+pub(crate) fn normal_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  mean: f64,
+  std_dev: f64,
+) -> Result<f64, NormalError> {
+  Ok(Normal::new(mean, std_dev)?.sample(rng))
+}
+
+/// Draws from a log-normal distribution, whose logarithm is normal with `mu` and `sigma`
+pub fn log_normal(mu: f64, sigma: f64) -> Result<f64, NormalError> {
+  log_normal_with(&mut rand::rng(), mu, sigma)
+}
+
+pub(crate) fn log_normal_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  mu: f64,
+  sigma: f64,
+) -> Result<f64, NormalError> {
+  Ok(LogNormal::new(mu, sigma)?.sample(rng))
+}
+
+/// Draws from an exponential distribution with rate `lambda` (mean `1 / lambda`)
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::dist::exponential;
+///
+/// // Seconds until the next request, at 20 requests per second on average
+/// let gap = exponential(20.0).unwrap();
+/// assert!(gap >= 0.0);
+/// ```
+pub fn exponential(lambda: f64) -> Result<f64, ExpError> {
+  exponential_with(&mut rand::rng(), lambda)
+}
+
+pub(crate) fn exponential_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  lambda: f64,
+) -> Result<f64, ExpError> {
+  Ok(Exp::new(lambda)?.sample(rng))
+}
+
+/// Draws the number of events in an interval from a Poisson distribution with mean `lambda`
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::dist::poisson;
+///
+/// let arrivals = poisson(4.5).unwrap();
+/// println!("{arrivals} requests this tick");
+/// ```
+pub fn poisson(lambda: f64) -> Result<u64, PoissonError> {
+  poisson_with(&mut rand::rng(), lambda)
+}
+
+pub(crate) fn poisson_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  lambda: f64,
+) -> Result<u64, PoissonError> {
+  Ok(Poisson::new(lambda)?.sample(rng) as u64)
+}
+
+/// Draws the number of successes in `n` trials with success probability `p`
+pub fn binomial(n: u64, p: f64) -> Result<u64, BinomialError> {
+  binomial_with(&mut rand::rng(), n, p)
+}
+
+pub(crate) fn binomial_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  n: u64,
+  p: f64,
+) -> Result<u64, BinomialError> {
+  Ok(Binomial::new(n, p)?.sample(rng))
+}
+
+/// Draws a rank in `1..=n` from a Zipf distribution with exponent `s`
+///
+/// Rank `k` is drawn with probability proportional to `1 / k^s`, which models
+/// the popularity of keys, pages or products.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::dist::zipf;
+///
+/// let product = zipf(1_000, 1.1).unwrap();
+/// assert!((1..=1_000).contains(&product));
+/// ```
+pub fn zipf(n: u64, s: f64) -> Result<u64, ZipfError> {
+  zipf_with(&mut rand::rng(), n, s)
+}
+
+pub(crate) fn zipf_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  n: u64,
+  s: f64,
+) -> Result<u64, ZipfError> {
+  Ok(Zipf::new(n as f64, s)?.sample(rng) as u64)
+}
+
+/// Draws from a Pareto distribution with minimum value `scale` and tail index `shape`
+pub fn pareto(scale: f64, shape: f64) -> Result<f64, ParetoError> {
+  pareto_with(&mut rand::rng(), scale, shape)
+}
+
+pub(crate) fn pareto_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  scale: f64,
+  shape: f64,
+) -> Result<f64, ParetoError> {
+  Ok(Pareto::new(scale, shape)?.sample(rng))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::rand::dist::{binomial, exponential, normal, poisson, zipf, Normal};
+  use crate::rand::Rng;
+
+  const N: usize = 100_000;
+
+  fn moments(samples: impl Iterator<Item = f64>) -> (f64, f64) {
+    let samples: Vec<f64> = samples.collect();
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, var)
+  }
+
+  /// Checks that `actual` is within `z` standard errors of `expected`
+  fn close(actual: f64, expected: f64, std_err: f64) {
+    let z = (actual - expected).abs() / std_err;
+    assert!(z < 5.0, "{actual} vs {expected} ({z:.1} standard errors)");
+  }
+
+  #[test]
+  fn test_normal() {
+    let mut rng = Rng::seeded(1);
+    let (mean, var) = moments((0..N).map(|_| rng.normal(10.0, 2.0).unwrap()));
+    close(mean, 10.0, 2.0 / (N as f64).sqrt());
+    close(var, 4.0, 4.0 * (2.0 / N as f64).sqrt());
+    assert!(normal(0.0, f64::NAN).is_err());
+    assert!(normal(0.0, 1.0).is_ok());
+  }
+
+  #[test]
+  fn test_log_normal() {
+    let mut rng = Rng::seeded(2);
+    let (mu, sigma) = (0.5f64, 0.4f64);
+    let (mean, _) = moments((0..N).map(|_| rng.log_normal(mu, sigma).unwrap()));
+    let expected = (mu + sigma * sigma / 2.0).exp();
+    let variance = ((sigma * sigma).exp() - 1.0) * (2.0 * mu + sigma * sigma).exp();
+    close(mean, expected, (variance / N as f64).sqrt());
+  }
+
+  #[test]
+  fn test_exponential() {
+    let mut rng = Rng::seeded(3);
+    let (mean, var) = moments((0..N).map(|_| rng.exponential(4.0).unwrap()));
+    close(mean, 0.25, 0.25 / (N as f64).sqrt());
+    close(var, 0.0625, 0.0625 * (8.0 / N as f64).sqrt());
+    assert!(exponential(-1.0).is_err());
+  }
+
+  #[test]
+  fn test_poisson() {
+    let mut rng = Rng::seeded(4);
+    let (mean, var) = moments((0..N).map(|_| rng.poisson(6.0).unwrap() as f64));
+    close(mean, 6.0, (6.0 / N as f64).sqrt());
+    close(var, 6.0, 6.0 * (2.0 / N as f64).sqrt());
+    assert!(poisson(0.0).is_err());
+  }
+
+  #[test]
+  fn test_binomial() {
+    let mut rng = Rng::seeded(5);
+    let (n, p) = (40u64, 0.3);
+    let (mean, var) = moments((0..N).map(|_| rng.binomial(n, p).unwrap() as f64));
+    let (expected_mean, expected_var) = (n as f64 * p, n as f64 * p * (1.0 - p));
+    close(mean, expected_mean, (expected_var / N as f64).sqrt());
+    close(var, expected_var, expected_var * (2.0 / N as f64).sqrt());
+    assert!(binomial(10, 1.5).is_err());
+  }
+
+  #[test]
+  fn test_zipf() {
+    let mut rng = Rng::seeded(6);
+    let (n, s) = (100u64, 1.2f64);
+    let harmonic: f64 = (1..=n).map(|k| (k as f64).powf(-s)).sum();
+    let samples: Vec<u64> = (0..N).map(|_| rng.zipf(n, s).unwrap()).collect();
+    assert!(samples.iter().all(|k| (1..=n).contains(k)));
+    let p1 = 1.0 / harmonic;
+    let share = samples.iter().filter(|&&k| k == 1).count() as f64 / N as f64;
+    close(share, p1, (p1 * (1.0 - p1) / N as f64).sqrt());
+    assert!(zipf(0, 1.0).is_err());
+  }
+
+  #[test]
+  fn test_pareto() {
+    let mut rng = Rng::seeded(7);
+    let (scale, shape) = (2.0, 5.0);
+    let samples: Vec<f64> = (0..N).map(|_| rng.pareto(scale, shape).unwrap()).collect();
+    assert!(samples.iter().all(|&x| x >= scale));
+    let (mean, _) = moments(samples.into_iter());
+    let expected = scale * shape / (shape - 1.0);
+    let variance = scale * scale * shape / ((shape - 1.0).powi(2) * (shape - 2.0));
+    close(mean, expected, (variance / N as f64).sqrt());
+  }
+
+  #[test]
+  fn test_reusable_distribution() {
+    use crate::rand::dist::Distribution;
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let (mut a, mut b) = (Rng::seeded(8), Rng::seeded(8));
+    assert_eq!(normal.sample(&mut a), normal.sample(&mut b));
+  }
+}
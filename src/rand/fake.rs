@@ -0,0 +1,600 @@
+//! Realistic fake data for test fixtures
+//!
+//! A [`Faker`] draws names, contact details, addresses, network identifiers,
+//! lorem ipsum text and dates from embedded per-locale data sets. Seeded
+//! fakers produce the same data on every run, and [`Faker::fake`] fills any
+//! type implementing [`serde::Deserialize`] by looking at its field names.
+//!
+//! The data sets may grow between releases, so pin fixtures with snapshots
+//! rather than relying on the exact output for a seed across versions.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use helpers::rand::fake::{Faker, Locale};
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct User {
+//!   id: u32,
+//!   first_name: String,
+//!   email: String,
+//!   city: String,
+//!   tags: Vec<String>,
+//!   created_at: String,
+//! }
+//!
+//! let mut faker = Faker::seeded(7, Locale::DeDe);
+//! let user: User = faker.fake().unwrap();
+//! assert!(user.email.contains("@example."));
+//! println!("{user:?}");
+//! ```
+
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rand::RngCore;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::{RangeError, Rng};
+use data::{HouseNumber, LocaleData};
+
+mod data;
+mod de;
+
+/// Error returned by [`Faker::fake`] when a type cannot be generated
+pub use serde::de::value::Error as FakeError;
+
+/// Language and country of the generated data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+  /// English (United States)
+  #[default]
+  EnUs,
+  /// English (United Kingdom)
+  EnGb,
+  /// German (Germany)
+  DeDe,
+  /// French (France)
+  FrFr,
+  /// Spanish (Spain)
+  EsEs,
+}
+
+impl Locale {
+  fn data(self) -> &'static LocaleData {
+    match self {
+      Locale::EnUs => &data::EN_US,
+      Locale::EnGb => &data::EN_GB,
+      Locale::DeDe => &data::DE_DE,
+      Locale::FrFr => &data::FR_FR,
+      Locale::EsEs => &data::ES_ES,
+    }
+  }
+}
+
+/// A postal address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Address {
+  /// Street name with a house number
+  pub street: String,
+  /// City or town
+  pub city: String,
+  /// Postal code in the locale's format
+  pub postal_code: String,
+  /// Country name in the locale's language
+  pub country: String,
+}
+
+/// A person with consistent name and email
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Person {
+  /// Given name
+  pub first_name: String,
+  /// Family name
+  pub last_name: String,
+  /// Email address derived from the name
+  pub email: String,
+  /// Phone number in a national format
+  pub phone: String,
+  /// Postal address
+  pub address: Address,
+}
+
+/// Fake data generator
+#[derive(Debug, Clone)]
+pub struct Faker {
+  rng: Rng,
+  locale: Locale,
+  /// `(enum, variant)` pairs found to recurse past the nesting limit
+  nested_variants: HashSet<(&'static str, &'static str)>,
+}
+
+impl Faker {
+  /// Creates a faker with a random seed
+  pub fn new(locale: Locale) -> Self {
+    Self::with_rng(Rng::from_entropy(), locale)
+  }
+
+  /// Creates a faker that produces the same data for the same seed
+  pub fn seeded(seed: u64, locale: Locale) -> Self {
+    Self::with_rng(Rng::seeded(seed), locale)
+  }
+
+  /// Creates a faker drawing from an existing generator
+  pub fn with_rng(rng: Rng, locale: Locale) -> Self {
+    Self {
+      rng,
+      locale,
+      nested_variants: HashSet::new(),
+    }
+  }
+
+  /// The locale of the generated data
+  pub fn locale(&self) -> Locale {
+    self.locale
+  }
+
+  /// Generates a value of any deserializable type
+  ///
+  /// Strings are chosen from the field name: `email`, `first_name`,
+  /// `last_name`, `name`, `username`, `phone`, `street`/`address`, `city`,
+  /// `postal_code`/`zip`, `country`, `ip`/`ipv6`, `url`, `domain`, `id`/`uuid`,
+  /// `password`/`token`, `*_at`/`timestamp` (RFC 3339), `birthday`/`date`
+  /// (`YYYY-MM-DD`), `title` and `description`/`body`, with lorem ipsum for
+  /// anything else. Integers named `age` or `year` stay in plausible ranges.
+  ///
+  /// Optional fields are occasionally `None` and collections hold one to three
+  /// items. Past a few levels of nesting, options are `None`, collections are
+  /// empty and enums only take variants that do not recurse. Types that rely
+  /// on `deserialize_any`, such as untagged enums, are given strings.
+  ///
+  /// # Errors
+  ///
+  /// Returns a [`FakeError`] if the type rejects the generated values, e.g.
+  /// a field with custom validation
+  pub fn fake<T: DeserializeOwned>(&mut self) -> Result<T, FakeError> {
+    loop {
+      let known = self.nested_variants.len();
+      match T::deserialize(de::FakeDeserializer::new(self)) {
+        // A recursive variant was found too deep; try again without it.
+        Err(_) if self.nested_variants.len() > known => {}
+        result => return result,
+      }
+    }
+  }
+
+  /// Generates a first name
+  pub fn first_name(&mut self) -> String {
+    self.pick(self.locale.data().first_names).to_string()
+  }
+
+  /// Generates a last name
+  pub fn last_name(&mut self) -> String {
+    self.pick(self.locale.data().last_names).to_string()
+  }
+
+  /// Generates a full name
+  pub fn name(&mut self) -> String {
+    format!("{} {}", self.first_name(), self.last_name())
+  }
+
+  /// Generates a lowercase ASCII username
+  pub fn username(&mut self) -> String {
+    let (first, last) = (
+      ascii_slug(&self.first_name()),
+      ascii_slug(&self.last_name()),
+    );
+    match self.rng.min_max(0, 2) {
+      0 => format!("{first}{last}"),
+      1 => format!("{first}_{last}"),
+      _ => format!("{first}{}", self.rng.min_max(1, 99)),
+    }
+  }
+
+  /// Generates an email address on a domain reserved for documentation
+  pub fn email(&mut self) -> String {
+    let (first, last) = (self.first_name(), self.last_name());
+    self.email_for(&first, &last)
+  }
+
+  fn email_for(&mut self, first: &str, last: &str) -> String {
+    let (first, last) = (ascii_slug(first), ascii_slug(last));
+    // A name without any ASCII letter, such as 李, leaves an empty slug.
+    let local = match (first.chars().next(), self.rng.min_max(0, 3)) {
+      (None, _) => last,
+      (_, 0) => format!("{first}.{last}"),
+      (_, 1) => format!("{first}{last}"),
+      (Some(initial), 2) => format!("{initial}{last}"),
+      _ => format!("{first}.{last}{}", self.rng.min_max(1, 99)),
+    };
+    format!("{local}@{}", self.pick(data::EMAIL_DOMAINS))
+  }
+
+  /// Generates a phone number in a national format
+  pub fn phone(&mut self) -> String {
+    let format = self.pick(self.locale.data().phone_formats);
+    self.pattern(format)
+  }
+
+  /// Generates a street name with a house number
+  pub fn street_address(&mut self) -> String {
+    let street = self.pick(self.locale.data().streets);
+    let number = self.rng.min_max(1, 250);
+    match self.locale.data().house_number {
+      HouseNumber::Before => format!("{number} {street}"),
+      HouseNumber::After => format!("{street} {number}"),
+      HouseNumber::AfterComma => format!("{street}, {number}"),
+    }
+  }
+
+  /// Generates a city name
+  pub fn city(&mut self) -> String {
+    self.pick(self.locale.data().cities).to_string()
+  }
+
+  /// Generates a postal code
+  pub fn postal_code(&mut self) -> String {
+    let format = self.pick(self.locale.data().postal_code_formats);
+    self.pattern(format)
+  }
+
+  /// The country of the locale, in its own language
+  pub fn country(&self) -> String {
+    self.locale.data().country.to_string()
+  }
+
+  /// Generates a complete address
+  pub fn address(&mut self) -> Address {
+    Address {
+      street: self.street_address(),
+      city: self.city(),
+      postal_code: self.postal_code(),
+      country: self.country(),
+    }
+  }
+
+  /// Generates a person whose email matches their name
+  pub fn person(&mut self) -> Person {
+    let (first_name, last_name) = (self.first_name(), self.last_name());
+    Person {
+      email: self.email_for(&first_name, &last_name),
+      phone: self.phone(),
+      address: self.address(),
+      first_name,
+      last_name,
+    }
+  }
+
+  /// Generates a unicast IPv4 address outside of `0.0.0.0/8` and `127.0.0.0/8`
+  pub fn ipv4(&mut self) -> Ipv4Addr {
+    let first = loop {
+      let octet = self.rng.min_max(1, 223) as u8;
+      if octet != 127 {
+        break octet;
+      }
+    };
+    let rest = self.rng.next_u32().to_le_bytes();
+    Ipv4Addr::new(first, rest[0], rest[1], rest[2])
+  }
+
+  /// Generates an IPv6 address
+  pub fn ipv6(&mut self) -> Ipv6Addr {
+    Ipv6Addr::from(u128::from(self.rng.next_u64()) << 64 | u128::from(self.rng.next_u64()))
+  }
+
+  /// Generates a domain name with a top-level domain of the locale
+  pub fn domain(&mut self) -> String {
+    let name = if self.rng.random_bool() {
+      ascii_slug(&self.last_name())
+    } else {
+      format!("{}{}", self.word(), self.word())
+    };
+    format!("{name}.{}", self.pick(self.locale.data().tlds))
+  }
+
+  /// Generates an HTTPS URL with a short path
+  pub fn url(&mut self) -> String {
+    format!("https://{}/{}/{}", self.domain(), self.word(), self.word())
+  }
+
+  /// Generates a lorem ipsum word
+  pub fn word(&mut self) -> String {
+    self.pick(data::LOREM).to_string()
+  }
+
+  /// Generates `count` lorem ipsum words
+  pub fn words(&mut self, count: usize) -> Vec<String> {
+    (0..count).map(|_| self.word()).collect()
+  }
+
+  /// Generates a capitalized lorem ipsum sentence of 4 to 12 words
+  pub fn sentence(&mut self) -> String {
+    let count = self.rng.min_max(4, 12) as usize;
+    let text = self.words(count).join(" ");
+    format!("{}{}.", text[..1].to_uppercase(), &text[1..])
+  }
+
+  /// Generates a paragraph of 3 to 6 sentences
+  pub fn paragraph(&mut self) -> String {
+    let count = self.rng.min_max(3, 6);
+    (0..count)
+      .map(|_| self.sentence())
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+
+  /// Generates a date between `start` and `end`, both included
+  ///
+  /// # Errors
+  ///
+  /// Returns `RangeError::EmptyRange` if `start` is after `end`
+  pub fn date_between(
+    &mut self,
+    start: NaiveDate,
+    end: NaiveDate,
+  ) -> Result<NaiveDate, RangeError> {
+    let days = self.rng.range(0..=(end - start).num_days())?;
+    Ok(start + chrono::Duration::days(days))
+  }
+
+  /// Generates an instant between `start` and `end`, both included, with millisecond precision
+  ///
+  /// # Errors
+  ///
+  /// Returns `RangeError::EmptyRange` if `start` is after `end`
+  pub fn datetime_between(
+    &mut self,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+  ) -> Result<DateTime<Utc>, RangeError> {
+    let millis = self
+      .rng
+      .range(start.timestamp_millis()..=end.timestamp_millis())?;
+    Ok(DateTime::from_timestamp_millis(millis).expect("between two valid instants"))
+  }
+
+  fn pick(&mut self, items: &'static [&'static str]) -> &'static str {
+    self
+      .rng
+      .random_choice(items)
+      .expect("data sets are not empty")
+  }
+
+  /// Replaces `#` with a digit and `?` with an uppercase letter
+  fn pattern(&mut self, format: &str) -> String {
+    format
+      .chars()
+      .map(|c| match c {
+        '#' => char::from(b'0' + self.rng.min_max(0, 9) as u8),
+        '?' => char::from(b'A' + self.rng.min_max(0, 25) as u8),
+        c => c,
+      })
+      .collect()
+  }
+}
+
+/// Lowercases and strips accents and anything else that does not belong in an email address
+fn ascii_slug(text: &str) -> String {
+  let mut slug = String::with_capacity(text.len());
+  for c in text.to_lowercase().chars() {
+    match c {
+      'a'..='z' | '0'..='9' => slug.push(c),
+      'ä' => slug.push_str("ae"),
+      'ö' => slug.push_str("oe"),
+      'ü' => slug.push_str("ue"),
+      'ß' => slug.push_str("ss"),
+      'à' | 'á' | 'â' => slug.push('a'),
+      'ç' => slug.push('c'),
+      'è' | 'é' | 'ê' | 'ë' => slug.push('e'),
+      'ì' | 'í' | 'î' | 'ï' => slug.push('i'),
+      'ñ' => slug.push('n'),
+      'ò' | 'ó' | 'ô' => slug.push('o'),
+      'ù' | 'ú' | 'û' => slug.push('u'),
+      'ÿ' => slug.push('y'),
+      _ => {}
+    }
+  }
+  slug
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use chrono::{DateTime, NaiveDate, Utc};
+  use serde::{Deserialize, Serialize};
+
+  use crate::rand::fake::{ascii_slug, Faker, Locale};
+  use crate::rand::RangeError;
+
+  #[test]
+  fn test_reproducible() {
+    let mut a = Faker::seeded(1, Locale::FrFr);
+    let mut b = Faker::seeded(1, Locale::FrFr);
+    assert_eq!(a.person(), b.person());
+    assert_eq!(a.paragraph(), b.paragraph());
+    assert_eq!(a.ipv6(), b.ipv6());
+    assert_ne!(
+      Faker::seeded(1, Locale::EnUs).person(),
+      Faker::seeded(2, Locale::EnUs).person()
+    );
+  }
+
+  #[test]
+  fn test_locales() {
+    for locale in [
+      Locale::EnUs,
+      Locale::EnGb,
+      Locale::DeDe,
+      Locale::FrFr,
+      Locale::EsEs,
+    ] {
+      let mut faker = Faker::seeded(3, locale);
+      for _ in 0..50 {
+        let person = faker.person();
+        let (local, domain) = person.email.split_once('@').unwrap();
+        assert!(domain.starts_with("example."));
+        assert!(local
+          .chars()
+          .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.'));
+        assert!(!person.phone.contains('#'));
+        assert!(!person.address.postal_code.contains('?'));
+        assert!(person.address.street.chars().any(|c| c.is_ascii_digit()));
+        assert!(faker.url().starts_with("https://"));
+        let ip = faker.ipv4();
+        assert!(!ip.is_loopback() && !ip.is_unspecified() && !ip.is_multicast());
+      }
+    }
+    let mut faker = Faker::seeded(4, Locale::DeDe);
+    assert_eq!(faker.country(), "Deutschland");
+    assert_eq!(faker.address().postal_code.len(), 5);
+    assert_eq!(ascii_slug("Jürgen Müller-Großé"), "juergenmuellergrosse");
+    for _ in 0..20 {
+      assert!(faker.email_for("李", "Weber").starts_with("weber@"));
+      assert!(faker.email_for("Émile", "Zola").starts_with('e'));
+    }
+  }
+
+  #[test]
+  fn test_text() {
+    let mut faker = Faker::seeded(5, Locale::EnUs);
+    let sentence = faker.sentence();
+    assert!(sentence.ends_with('.'));
+    assert!(sentence.chars().next().unwrap().is_uppercase());
+    assert_eq!(faker.words(7).len(), 7);
+    assert!(faker.paragraph().matches('.').count() >= 3);
+  }
+
+  #[test]
+  fn test_dates() {
+    let mut faker = Faker::seeded(6, Locale::EnUs);
+    let (start, end) = (
+      NaiveDate::from_ymd_opt(2020, 2, 28).unwrap(),
+      NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(),
+    );
+    for _ in 0..100 {
+      let date = faker.date_between(start, end).unwrap();
+      assert!(start <= date && date <= end);
+    }
+    assert_eq!(faker.date_between(end, start), Err(RangeError::EmptyRange));
+    let moment = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    assert_eq!(faker.datetime_between(moment, moment), Ok(moment));
+    let later: DateTime<Utc> = DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+    let between = faker.datetime_between(moment, later).unwrap();
+    assert!(moment <= between && between <= later);
+  }
+
+  #[derive(Debug, PartialEq, Serialize, Deserialize)]
+  enum Role {
+    Admin,
+    Member { since: u16 },
+  }
+
+  #[derive(Debug, PartialEq, Serialize, Deserialize)]
+  struct Account {
+    id: String,
+    username: String,
+    email: String,
+    age: u8,
+    score: f64,
+    active: bool,
+    role: Role,
+    ip: String,
+    homepage: Option<String>,
+    created_at: String,
+    birthday: String,
+    address: crate::rand::fake::Address,
+    tags: Vec<String>,
+    limits: HashMap<String, u32>,
+    pair: (i16, char),
+    children: Vec<Account>,
+  }
+
+  #[test]
+  fn test_fake_serde() {
+    let mut faker = Faker::seeded(8, Locale::EsEs);
+    let account: Account = faker.fake().unwrap();
+    assert_eq!(account.id.len(), 36);
+    assert!(account.email.contains('@'));
+    assert!((18..=90).contains(&account.age));
+    assert!(account.ip.parse::<std::net::Ipv4Addr>().is_ok());
+    assert!(DateTime::parse_from_rfc3339(&account.created_at).is_ok());
+    assert!(NaiveDate::parse_from_str(&account.birthday, "%Y-%m-%d").is_ok());
+    assert!((1..=3).contains(&account.tags.len()));
+    assert_eq!(account.address.country, "España");
+
+    let again: Account = Faker::seeded(8, Locale::EsEs).fake().unwrap();
+    assert_eq!(account, again);
+    // Serializes back, so fixtures can be written out as JSON
+    assert!(serde_json::to_string(&account).is_ok());
+
+    let people: Vec<crate::rand::fake::Person> = faker.fake().unwrap();
+    assert!(!people.is_empty());
+  }
+
+  #[allow(dead_code)]
+  #[derive(Debug, Deserialize)]
+  enum Tree {
+    Leaf(u32),
+    Node(Box<Tree>, Box<Tree>, Box<Tree>),
+  }
+
+  impl Tree {
+    fn height(&self) -> usize {
+      match self {
+        Tree::Leaf(_) => 1,
+        Tree::Node(a, b, c) => 1 + a.height().max(b.height()).max(c.height()),
+      }
+    }
+  }
+
+  #[allow(dead_code)]
+  #[derive(Debug, Deserialize)]
+  enum List {
+    Nil,
+    Cons(u8, Box<List>),
+    Wrap(Box<List>),
+  }
+
+  #[allow(dead_code)]
+  #[derive(Debug, Deserialize)]
+  enum Endless {
+    More(Box<Endless>),
+  }
+
+  #[derive(Debug, Deserialize)]
+  struct Counters {
+    age: u8,
+    min_age: u16,
+    page: u32,
+    usage: u32,
+  }
+
+  #[test]
+  fn test_fake_ages() {
+    let mut faker = Faker::seeded(10, Locale::EnUs);
+    let (mut page, mut usage) = (false, false);
+    for _ in 0..100 {
+      let counters: Counters = faker.fake().unwrap();
+      assert!((18..=90).contains(&counters.age));
+      assert!((18..=90).contains(&counters.min_age));
+      page |= !(18..=90).contains(&counters.page);
+      usage |= !(18..=90).contains(&counters.usage);
+    }
+    assert!(page && usage);
+  }
+
+  #[test]
+  fn test_fake_recursive() {
+    let mut faker = Faker::seeded(9, Locale::EnUs);
+    for _ in 0..50 {
+      let tree: Tree = faker.fake().unwrap();
+      assert!(tree.height() <= 8, "{}", tree.height());
+      let _: List = faker.fake().unwrap();
+    }
+    assert!(faker.fake::<Endless>().is_err());
+  }
+}
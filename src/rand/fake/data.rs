@@ -0,0 +1,586 @@
+//! Embedded data sets for [`Faker`](super::Faker)
+//!
+//! Phone and postal code formats use `#` for a digit and `?` for an uppercase letter.
+
+/// Where the house number goes relative to the street name
+#[derive(Debug, Clone, Copy)]
+pub(super) enum HouseNumber {
+  /// `221 Baker Street`
+  Before,
+  /// `Hauptstraße 12`
+  After,
+  /// `Calle Mayor, 12`
+  AfterComma,
+}
+
+pub(super) struct LocaleData {
+  pub first_names: &'static [&'static str],
+  pub last_names: &'static [&'static str],
+  pub cities: &'static [&'static str],
+  pub streets: &'static [&'static str],
+  pub house_number: HouseNumber,
+  pub phone_formats: &'static [&'static str],
+  pub postal_code_formats: &'static [&'static str],
+  pub country: &'static str,
+  pub tlds: &'static [&'static str],
+}
+
+pub(super) static EN_US: LocaleData = LocaleData {
+  first_names: &[
+    "James",
+    "Mary",
+    "Robert",
+    "Patricia",
+    "John",
+    "Jennifer",
+    "Michael",
+    "Linda",
+    "David",
+    "Elizabeth",
+    "William",
+    "Barbara",
+    "Richard",
+    "Susan",
+    "Joseph",
+    "Jessica",
+    "Thomas",
+    "Sarah",
+    "Christopher",
+    "Karen",
+    "Daniel",
+    "Emily",
+    "Matthew",
+    "Ashley",
+    "Anthony",
+    "Olivia",
+    "Mark",
+    "Sophia",
+    "Steven",
+    "Madison",
+    "Andrew",
+    "Abigail",
+    "Joshua",
+    "Chloe",
+    "Kevin",
+    "Grace",
+  ],
+  last_names: &[
+    "Smith",
+    "Johnson",
+    "Williams",
+    "Brown",
+    "Jones",
+    "Garcia",
+    "Miller",
+    "Davis",
+    "Rodriguez",
+    "Martinez",
+    "Hernandez",
+    "Lopez",
+    "Wilson",
+    "Anderson",
+    "Thomas",
+    "Taylor",
+    "Moore",
+    "Jackson",
+    "Martin",
+    "Lee",
+    "Thompson",
+    "White",
+    "Harris",
+    "Clark",
+    "Lewis",
+    "Robinson",
+    "Walker",
+    "Young",
+    "Allen",
+    "King",
+    "Wright",
+    "Scott",
+    "Green",
+    "Baker",
+    "Adams",
+    "Nelson",
+  ],
+  cities: &[
+    "New York",
+    "Los Angeles",
+    "Chicago",
+    "Houston",
+    "Phoenix",
+    "Philadelphia",
+    "San Antonio",
+    "San Diego",
+    "Dallas",
+    "Austin",
+    "Jacksonville",
+    "Columbus",
+    "Charlotte",
+    "Indianapolis",
+    "Seattle",
+    "Denver",
+    "Boston",
+    "Nashville",
+    "Portland",
+    "Atlanta",
+    "Minneapolis",
+    "Raleigh",
+  ],
+  streets: &[
+    "Main Street",
+    "Oak Avenue",
+    "Maple Drive",
+    "Cedar Lane",
+    "Elm Street",
+    "Pine Street",
+    "Washington Avenue",
+    "Lake Road",
+    "Hill Street",
+    "Park Avenue",
+    "Sunset Boulevard",
+    "Church Street",
+    "Highland Avenue",
+    "River Road",
+    "Spring Street",
+    "Walnut Street",
+  ],
+  house_number: HouseNumber::Before,
+  phone_formats: &["(###) ###-####", "###-###-####", "+1 ###-###-####"],
+  postal_code_formats: &["#####", "#####-####"],
+  country: "United States",
+  tlds: &["com", "net", "org", "io"],
+};
+
+pub(super) static EN_GB: LocaleData = LocaleData {
+  first_names: &[
+    "Oliver", "Amelia", "George", "Isla", "Harry", "Ava", "Jack", "Mia", "Charlie", "Ivy", "Noah",
+    "Lily", "Leo", "Freya", "Arthur", "Florence", "Oscar", "Evie", "Alfie", "Poppy", "Henry",
+    "Sophie", "Archie", "Ella", "Theo", "Rosie", "Thomas", "Grace", "Edward", "Alice",
+  ],
+  last_names: &[
+    "Smith", "Jones", "Taylor", "Brown", "Williams", "Wilson", "Johnson", "Davies", "Patel",
+    "Robinson", "Wright", "Thompson", "Evans", "Walker", "White", "Roberts", "Green", "Hall",
+    "Wood", "Jackson", "Clarke", "Hughes", "Edwards", "Turner", "Hill", "Cooper", "Ward", "Morris",
+  ],
+  cities: &[
+    "London",
+    "Birmingham",
+    "Manchester",
+    "Leeds",
+    "Glasgow",
+    "Liverpool",
+    "Bristol",
+    "Sheffield",
+    "Edinburgh",
+    "Cardiff",
+    "Leicester",
+    "Nottingham",
+    "Newcastle",
+    "Brighton",
+    "Oxford",
+    "Cambridge",
+    "York",
+    "Bath",
+    "Belfast",
+    "Southampton",
+  ],
+  streets: &[
+    "High Street",
+    "Station Road",
+    "Church Lane",
+    "Victoria Road",
+    "Green Lane",
+    "Manor Road",
+    "Park Road",
+    "Queens Road",
+    "Kings Road",
+    "New Road",
+    "Mill Lane",
+    "School Lane",
+    "The Crescent",
+    "Baker Street",
+    "London Road",
+    "Chapel Street",
+  ],
+  house_number: HouseNumber::Before,
+  phone_formats: &[
+    "07### ######",
+    "01### ######",
+    "020 #### ####",
+    "+44 7### ######",
+  ],
+  postal_code_formats: &["?# #??", "?## #??", "??# #??", "??## #??"],
+  country: "United Kingdom",
+  tlds: &["co.uk", "org.uk", "com", "uk"],
+};
+
+pub(super) static DE_DE: LocaleData = LocaleData {
+  first_names: &[
+    "Lukas",
+    "Anna",
+    "Leon",
+    "Lea",
+    "Finn",
+    "Hannah",
+    "Jonas",
+    "Emma",
+    "Paul",
+    "Mia",
+    "Felix",
+    "Sophie",
+    "Maximilian",
+    "Marie",
+    "Elias",
+    "Lena",
+    "Noah",
+    "Laura",
+    "Ben",
+    "Julia",
+    "Jürgen",
+    "Sabine",
+    "Stefan",
+    "Katrin",
+    "Matthias",
+    "Jörg",
+    "Ute",
+    "Björn",
+    "Käthe",
+    "Günter",
+  ],
+  last_names: &[
+    "Müller",
+    "Schmidt",
+    "Schneider",
+    "Fischer",
+    "Weber",
+    "Meyer",
+    "Wagner",
+    "Becker",
+    "Schulz",
+    "Hoffmann",
+    "Schäfer",
+    "Koch",
+    "Bauer",
+    "Richter",
+    "Klein",
+    "Wolf",
+    "Schröder",
+    "Neumann",
+    "Schwarz",
+    "Zimmermann",
+    "Braun",
+    "Krüger",
+    "Hofmann",
+    "Hartmann",
+    "Lange",
+    "Schmitt",
+  ],
+  cities: &[
+    "Berlin",
+    "Hamburg",
+    "München",
+    "Köln",
+    "Frankfurt am Main",
+    "Stuttgart",
+    "Düsseldorf",
+    "Leipzig",
+    "Dortmund",
+    "Essen",
+    "Bremen",
+    "Dresden",
+    "Hannover",
+    "Nürnberg",
+    "Duisburg",
+    "Bochum",
+    "Wuppertal",
+    "Bielefeld",
+    "Bonn",
+    "Münster",
+  ],
+  streets: &[
+    "Hauptstraße",
+    "Schulstraße",
+    "Gartenstraße",
+    "Bahnhofstraße",
+    "Dorfstraße",
+    "Bergstraße",
+    "Birkenweg",
+    "Lindenstraße",
+    "Kirchstraße",
+    "Waldstraße",
+    "Ringstraße",
+    "Schillerstraße",
+    "Goethestraße",
+    "Am Markt",
+    "Mühlenweg",
+    "Wiesenweg",
+  ],
+  house_number: HouseNumber::After,
+  phone_formats: &[
+    "01## #######",
+    "030 ########",
+    "089 ########",
+    "+49 1## #######",
+  ],
+  postal_code_formats: &["#####"],
+  country: "Deutschland",
+  tlds: &["de", "com", "net"],
+};
+
+pub(super) static FR_FR: LocaleData = LocaleData {
+  first_names: &[
+    "Gabriel",
+    "Louise",
+    "Raphaël",
+    "Jade",
+    "Léo",
+    "Ambre",
+    "Louis",
+    "Alice",
+    "Lucas",
+    "Emma",
+    "Adam",
+    "Rose",
+    "Arthur",
+    "Chloé",
+    "Hugo",
+    "Léa",
+    "Jules",
+    "Manon",
+    "Maël",
+    "Inès",
+    "Noé",
+    "Camille",
+    "Théo",
+    "Zoé",
+    "François",
+    "Hélène",
+    "Stéphane",
+    "Cécile",
+    "Jérôme",
+    "Agnès",
+  ],
+  last_names: &[
+    "Martin", "Bernard", "Thomas", "Petit", "Robert", "Richard", "Durand", "Dubois", "Moreau",
+    "Laurent", "Simon", "Michel", "Lefèvre", "Leroy", "Roux", "David", "Bertrand", "Morel",
+    "Fournier", "Girard", "Bonnet", "Dupont", "Lambert", "Fontaine", "Rousseau", "Vincent",
+  ],
+  cities: &[
+    "Paris",
+    "Marseille",
+    "Lyon",
+    "Toulouse",
+    "Nice",
+    "Nantes",
+    "Montpellier",
+    "Strasbourg",
+    "Bordeaux",
+    "Lille",
+    "Rennes",
+    "Reims",
+    "Toulon",
+    "Saint-Étienne",
+    "Le Havre",
+    "Grenoble",
+    "Dijon",
+    "Angers",
+    "Nîmes",
+    "Aix-en-Provence",
+  ],
+  streets: &[
+    "rue de la Paix",
+    "rue Victor Hugo",
+    "avenue Jean Jaurès",
+    "rue de la République",
+    "boulevard Pasteur",
+    "rue du Moulin",
+    "place de l'Église",
+    "rue des Écoles",
+    "rue de la Gare",
+    "avenue de la Liberté",
+    "rue Nationale",
+    "chemin des Vignes",
+    "rue du Château",
+    "allée des Tilleuls",
+    "rue Pasteur",
+    "impasse des Lilas",
+  ],
+  house_number: HouseNumber::Before,
+  phone_formats: &["0# ## ## ## ##", "+33 # ## ## ## ##"],
+  postal_code_formats: &["#####"],
+  country: "France",
+  tlds: &["fr", "com", "net"],
+};
+
+pub(super) static ES_ES: LocaleData = LocaleData {
+  first_names: &[
+    "Hugo",
+    "Lucía",
+    "Martín",
+    "Sofía",
+    "Lucas",
+    "Martina",
+    "Mateo",
+    "María",
+    "Leo",
+    "Julia",
+    "Daniel",
+    "Paula",
+    "Alejandro",
+    "Valeria",
+    "Pablo",
+    "Emma",
+    "Manuel",
+    "Daniela",
+    "Álvaro",
+    "Carmen",
+    "Javier",
+    "Ana",
+    "José",
+    "Isabel",
+    "Íñigo",
+    "Begoña",
+    "Sergio",
+    "Nuria",
+  ],
+  last_names: &[
+    "García",
+    "Rodríguez",
+    "González",
+    "Fernández",
+    "López",
+    "Martínez",
+    "Sánchez",
+    "Pérez",
+    "Gómez",
+    "Martín",
+    "Jiménez",
+    "Ruiz",
+    "Hernández",
+    "Díaz",
+    "Moreno",
+    "Muñoz",
+    "Álvarez",
+    "Romero",
+    "Alonso",
+    "Gutiérrez",
+    "Navarro",
+    "Torres",
+    "Domínguez",
+    "Vázquez",
+    "Ramos",
+  ],
+  cities: &[
+    "Madrid",
+    "Barcelona",
+    "Valencia",
+    "Sevilla",
+    "Zaragoza",
+    "Málaga",
+    "Murcia",
+    "Palma",
+    "Las Palmas",
+    "Bilbao",
+    "Alicante",
+    "Córdoba",
+    "Valladolid",
+    "Vigo",
+    "Gijón",
+    "Granada",
+    "A Coruña",
+    "Vitoria",
+    "Santander",
+    "Salamanca",
+  ],
+  streets: &[
+    "Calle Mayor",
+    "Calle Real",
+    "Plaza de España",
+    "Avenida de la Constitución",
+    "Calle de Alcalá",
+    "Gran Vía",
+    "Calle del Sol",
+    "Paseo de la Castellana",
+    "Calle Nueva",
+    "Calle de la Iglesia",
+    "Avenida de Andalucía",
+    "Calle San Juan",
+    "Calle del Carmen",
+    "Camino Real",
+    "Calle de Goya",
+    "Rambla de Cataluña",
+  ],
+  house_number: HouseNumber::AfterComma,
+  phone_formats: &["6## ### ###", "9## ## ## ##", "+34 6## ### ###"],
+  postal_code_formats: &["0####", "1####", "2####", "3####", "4####"],
+  country: "España",
+  tlds: &["es", "com", "net"],
+};
+
+/// Domains reserved for documentation, so fake addresses never reach a real inbox
+pub(super) static EMAIL_DOMAINS: &[&str] = &["example.com", "example.net", "example.org"];
+
+pub(super) static LOREM: &[&str] = &[
+  "lorem",
+  "ipsum",
+  "dolor",
+  "sit",
+  "amet",
+  "consectetur",
+  "adipiscing",
+  "elit",
+  "sed",
+  "do",
+  "eiusmod",
+  "tempor",
+  "incididunt",
+  "ut",
+  "labore",
+  "et",
+  "dolore",
+  "magna",
+  "aliqua",
+  "enim",
+  "ad",
+  "minim",
+  "veniam",
+  "quis",
+  "nostrud",
+  "exercitation",
+  "ullamco",
+  "laboris",
+  "nisi",
+  "aliquip",
+  "ex",
+  "ea",
+  "commodo",
+  "consequat",
+  "duis",
+  "aute",
+  "irure",
+  "in",
+  "reprehenderit",
+  "voluptate",
+  "velit",
+  "esse",
+  "cillum",
+  "fugiat",
+  "nulla",
+  "pariatur",
+  "excepteur",
+  "sint",
+  "occaecat",
+  "cupidatat",
+  "non",
+  "proident",
+  "sunt",
+  "culpa",
+  "qui",
+  "officia",
+  "deserunt",
+  "mollit",
+  "anim",
+  "id",
+  "est",
+  "laborum",
+];
@@ -0,0 +1,462 @@
+//! A serde `Deserializer` that invents its input, used by [`Faker::fake`](super::Faker::fake)
+
+use chrono::{DateTime, NaiveDate};
+use rand::RngCore;
+use serde::de::value::Error;
+use serde::de::{
+  DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use serde::Deserializer;
+
+use super::Faker;
+
+/// Nesting level after which options are `None`, collections are empty and
+/// enums avoid variants known to recurse, so that recursive types terminate
+const MAX_DEPTH: usize = 6;
+
+pub(super) struct FakeDeserializer<'a> {
+  faker: &'a mut Faker,
+  /// Lowercased name of the field being generated
+  field: String,
+  depth: usize,
+  /// Enum variants entered past [`MAX_DEPTH`], outermost first
+  enums: Vec<(&'static str, &'static str)>,
+}
+
+impl<'a> FakeDeserializer<'a> {
+  pub(super) fn new(faker: &'a mut Faker) -> Self {
+    Self {
+      faker,
+      field: String::new(),
+      depth: 0,
+      enums: Vec::new(),
+    }
+  }
+
+  fn child(&mut self, field: &str) -> FakeDeserializer<'_> {
+    FakeDeserializer {
+      faker: &mut *self.faker,
+      field: field.to_ascii_lowercase(),
+      depth: self.depth + 1,
+      enums: self.enums.clone(),
+    }
+  }
+
+  fn is(&self, names: &[&str]) -> bool {
+    names.iter().any(|name| self.field.contains(name))
+  }
+
+  fn text(&mut self) -> String {
+    let field = self.field.as_str();
+    if self.is(&["email"]) {
+      self.faker.email()
+    } else if self.is(&["username", "login", "handle"]) {
+      self.faker.username()
+    } else if self.is(&["first_name", "firstname", "given_name"]) {
+      self.faker.first_name()
+    } else if self.is(&["last_name", "lastname", "surname", "family_name"]) {
+      self.faker.last_name()
+    } else if self.is(&["domain", "host"]) {
+      self.faker.domain()
+    } else if self.is(&["url", "website", "homepage", "link"]) {
+      self.faker.url()
+    } else if self.is(&["name"]) {
+      self.faker.name()
+    } else if self.is(&["phone", "mobile"]) {
+      self.faker.phone()
+    } else if self.is(&["ipv6"]) {
+      self.faker.ipv6().to_string()
+    } else if field == "ip" || field.ends_with("_ip") || self.is(&["ipv4", "ip_address"]) {
+      self.faker.ipv4().to_string()
+    } else if self.is(&["street", "address"]) {
+      self.faker.street_address()
+    } else if self.is(&["city", "town"]) {
+      self.faker.city()
+    } else if self.is(&["zip", "postal", "postcode"]) {
+      self.faker.postal_code()
+    } else if self.is(&["country"]) {
+      self.faker.country()
+    } else if field == "id" || field.ends_with("_id") || self.is(&["uuid"]) {
+      let mut bytes = [0; 16];
+      self.faker.rng.fill_bytes(&mut bytes);
+      uuid::Builder::from_random_bytes(bytes)
+        .into_uuid()
+        .to_string()
+    } else if self.is(&["password", "secret", "token"]) {
+      (0..16)
+        .map(|_| {
+          let i = self.faker.rng.min_max(0, 61) as u8;
+          char::from(match i {
+            0..=9 => b'0' + i,
+            10..=35 => b'a' + i - 10,
+            _ => b'A' + i - 36,
+          })
+        })
+        .collect()
+    } else if field.ends_with("_at") || self.is(&["timestamp", "time"]) {
+      let (start, end) = (1_420_070_400_000, 1_767_225_599_999); // 2015 to 2025
+      let millis = self.faker.rng.range(start..=end).expect("valid range");
+      DateTime::from_timestamp_millis(millis)
+        .expect("valid instant")
+        .to_rfc3339()
+    } else if self.is(&["birth"]) || field == "dob" {
+      self.date(1950, 2005)
+    } else if self.is(&["date"]) {
+      self.date(2015, 2025)
+    } else if self.is(&["title", "subject", "summary"]) {
+      self.faker.sentence()
+    } else if self.is(&["description", "body", "content", "bio", "text", "comment"]) {
+      self.faker.paragraph()
+    } else {
+      let count = self.faker.rng.min_max(1, 2) as usize;
+      self.faker.words(count).join(" ")
+    }
+  }
+
+  fn date(&mut self, from_year: i32, to_year: i32) -> String {
+    let start = NaiveDate::from_ymd_opt(from_year, 1, 1).expect("valid date");
+    let end = NaiveDate::from_ymd_opt(to_year, 12, 31).expect("valid date");
+    let date = self.faker.date_between(start, end).expect("valid range");
+    date.format("%Y-%m-%d").to_string()
+  }
+
+  fn integer(&mut self, max: u64) -> u64 {
+    let field = self.field.as_str();
+    let (low, high) = if field == "age" || field.ends_with("_age") {
+      (18, 90)
+    } else if self.is(&["year"]) && max >= 2030 {
+      (1970, 2030)
+    } else {
+      (0, max.min(10_000))
+    };
+    self.faker.rng.range(low..=high).expect("valid range")
+  }
+
+  fn float(&mut self) -> f64 {
+    let (low, high) = if self.is(&["latitude"]) || self.field == "lat" {
+      (-90.0, 90.0)
+    } else if self.is(&["longitude"]) || self.field == "lng" || self.field == "lon" {
+      (-180.0, 180.0)
+    } else {
+      (0.0, 1000.0)
+    };
+    let value: f64 = self.faker.rng.range(low..=high).expect("valid range");
+    if self.is(&["price", "amount", "cost", "balance"]) {
+      (value * 100.0).round() / 100.0
+    } else {
+      value
+    }
+  }
+
+  fn len(&mut self) -> usize {
+    if self.depth >= MAX_DEPTH {
+      0
+    } else {
+      self.faker.rng.min_max(1, 3) as usize
+    }
+  }
+}
+
+macro_rules! integer {
+  ($($method:ident => $visit:ident: $ty:ty),* $(,)?) => {
+    $(
+      fn $method<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        let value = self.integer(<$ty>::MAX as u64);
+        visitor.$visit(value as $ty)
+      }
+    )*
+  };
+}
+
+impl<'de> Deserializer<'de> for FakeDeserializer<'_> {
+  type Error = Error;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    self.deserialize_string(visitor)
+  }
+
+  fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_bool(self.faker.rng.random_bool())
+  }
+
+  integer! {
+    deserialize_i8 => visit_i8: i8,
+    deserialize_i16 => visit_i16: i16,
+    deserialize_i32 => visit_i32: i32,
+    deserialize_i64 => visit_i64: i64,
+    deserialize_i128 => visit_i128: i128,
+    deserialize_u8 => visit_u8: u8,
+    deserialize_u16 => visit_u16: u16,
+    deserialize_u32 => visit_u32: u32,
+    deserialize_u64 => visit_u64: u64,
+    deserialize_u128 => visit_u128: u128,
+  }
+
+  fn deserialize_f32<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_f32(self.float() as f32)
+  }
+
+  fn deserialize_f64<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_f64(self.float())
+  }
+
+  fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_char(char::from(b'a' + self.faker.rng.min_max(0, 25) as u8))
+  }
+
+  fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    self.deserialize_string(visitor)
+  }
+
+  fn deserialize_string<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_string(self.text())
+  }
+
+  fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    self.deserialize_byte_buf(visitor)
+  }
+
+  fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    let mut bytes = vec![0; 16];
+    self.faker.rng.fill_bytes(&mut bytes);
+    visitor.visit_byte_buf(bytes)
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    if self.depth >= MAX_DEPTH || self.faker.rng.min_max(0, 4) == 0 {
+      visitor.visit_none()
+    } else {
+      visitor.visit_some(self)
+    }
+  }
+
+  fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_unit()
+  }
+
+  fn deserialize_unit_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    visitor.visit_unit()
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_seq<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+    let remaining = self.len();
+    visitor.visit_seq(Seq {
+      de: self,
+      remaining,
+    })
+  }
+
+  fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_seq(Seq {
+      de: self,
+      remaining: len,
+    })
+  }
+
+  fn deserialize_tuple_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    len: usize,
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    self.deserialize_tuple(len, visitor)
+  }
+
+  fn deserialize_map<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+    let remaining = self.len();
+    visitor.visit_map(Map {
+      de: self,
+      remaining,
+    })
+  }
+
+  fn deserialize_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    visitor.visit_map(Struct {
+      de: self,
+      fields: fields.iter(),
+      current: "",
+    })
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(
+    mut self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    if self.depth < MAX_DEPTH {
+      let variant = *self
+        .faker
+        .rng
+        .random_choice(variants)
+        .ok_or_else(|| <Error as serde::de::Error>::custom("enum has no variants"))?;
+      return visitor.visit_enum(Enum { de: self, variant });
+    }
+    // Serde does not tell which variants hold content, so a variant that leads
+    // back to its own enum is remembered and `Faker::fake` tries again.
+    if let Some(&outer) = self.enums.iter().find(|(enum_name, _)| *enum_name == name) {
+      self.faker.nested_variants.insert(outer);
+      return Err(<Error as serde::de::Error>::custom(format!(
+        "enum {name} recurses past the nesting limit"
+      )));
+    }
+    let candidates: Vec<&'static str> = variants
+      .iter()
+      .copied()
+      .filter(|variant| !self.faker.nested_variants.contains(&(name, variant)))
+      .collect();
+    let Some(&variant) = self.faker.rng.random_choice(&candidates) else {
+      // The enclosing variant cannot be completed either.
+      if let Some(&outer) = self.enums.last() {
+        self.faker.nested_variants.insert(outer);
+      }
+      return Err(<Error as serde::de::Error>::custom(format!(
+        "enum {name} has no variant without nested content"
+      )));
+    };
+    self.enums.push((name, variant));
+    visitor.visit_enum(Enum { de: self, variant })
+  }
+
+  fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    self.deserialize_string(visitor)
+  }
+
+  fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_unit()
+  }
+}
+
+struct Seq<'a> {
+  de: FakeDeserializer<'a>,
+  remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for Seq<'_> {
+  type Error = Error;
+
+  fn next_element_seed<T: DeserializeSeed<'de>>(
+    &mut self,
+    seed: T,
+  ) -> Result<Option<T::Value>, Error> {
+    if self.remaining == 0 {
+      return Ok(None);
+    }
+    self.remaining -= 1;
+    // Elements keep the field name, so `emails: Vec<String>` holds emails.
+    let field = self.de.field.clone();
+    seed.deserialize(self.de.child(&field)).map(Some)
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    Some(self.remaining)
+  }
+}
+
+struct Map<'a> {
+  de: FakeDeserializer<'a>,
+  remaining: usize,
+}
+
+impl<'de> MapAccess<'de> for Map<'_> {
+  type Error = Error;
+
+  fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+    if self.remaining == 0 {
+      return Ok(None);
+    }
+    self.remaining -= 1;
+    seed.deserialize(self.de.child("")).map(Some)
+  }
+
+  fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+    seed.deserialize(self.de.child(""))
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    Some(self.remaining)
+  }
+}
+
+struct Struct<'a> {
+  de: FakeDeserializer<'a>,
+  fields: std::slice::Iter<'static, &'static str>,
+  current: &'static str,
+}
+
+impl<'de> MapAccess<'de> for Struct<'_> {
+  type Error = Error;
+
+  fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+    match self.fields.next() {
+      Some(field) => {
+        self.current = field;
+        seed
+          .deserialize(IntoDeserializer::<Error>::into_deserializer(*field))
+          .map(Some)
+      }
+      None => Ok(None),
+    }
+  }
+
+  fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+    seed.deserialize(self.de.child(self.current))
+  }
+}
+
+struct Enum<'a> {
+  de: FakeDeserializer<'a>,
+  variant: &'static str,
+}
+
+impl<'de> EnumAccess<'de> for Enum<'_> {
+  type Error = Error;
+  type Variant = Self;
+
+  fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+    let variant = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+    Ok((variant, self))
+  }
+}
+
+impl<'de> VariantAccess<'de> for Enum<'_> {
+  type Error = Error;
+
+  fn unit_variant(self) -> Result<(), Error> {
+    Ok(())
+  }
+
+  fn newtype_variant_seed<T: DeserializeSeed<'de>>(mut self, seed: T) -> Result<T::Value, Error> {
+    let field = self.de.field.clone();
+    seed.deserialize(self.de.child(&field))
+  }
+
+  fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+    self.de.deserialize_tuple(len, visitor)
+  }
+
+  fn struct_variant<V: Visitor<'de>>(
+    self,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    self.de.deserialize_struct("", fields, visitor)
+  }
+}
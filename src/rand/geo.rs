@@ -0,0 +1,321 @@
+//! Uniform random points, unit vectors and coordinates
+//!
+//! Every shape is sampled uniformly by area: points do not bunch up at the
+//! center of a circle or at the poles of a sphere.
+
+use std::f64::consts::TAU;
+
+use rand::{Rng as _, RngCore};
+use rand_distr::{Distribution, StandardNormal};
+
+use super::{RangeError, SampleRange};
+
+/// Generates a point uniformly within a rectangle
+///
+/// # Returns
+///
+/// `[x, y]`, or an error if a range is empty or not finite
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::geo::point_in_rect;
+///
+/// let [x, y] = point_in_rect(0.0..800.0, 0.0..=600.0).unwrap();
+/// assert!((0.0..800.0).contains(&x) && (0.0..=600.0).contains(&y));
+/// ```
+pub fn point_in_rect<X, Y>(x: X, y: Y) -> Result<[f64; 2], RangeError>
+where
+  X: SampleRange<f64>,
+  Y: SampleRange<f64>,
+{
+  point_in_rect_with(&mut rand::rng(), x, y)
+}
+
+pub(crate) fn point_in_rect_with<R, X, Y>(rng: &mut R, x: X, y: Y) -> Result<[f64; 2], RangeError>
+where
+  R: RngCore + ?Sized,
+  X: SampleRange<f64>,
+  Y: SampleRange<f64>,
+{
+  Ok([x.sample_single(rng)?, y.sample_single(rng)?])
+}
+
+/// Generates a point uniformly within a circle
+///
+/// # Returns
+///
+/// `[x, y]`, or an error if `radius` is negative or not finite
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::geo::point_in_circle;
+///
+/// let [x, y] = point_in_circle([10.0, 10.0], 5.0).unwrap();
+/// assert!((x - 10.0).hypot(y - 10.0) <= 5.0);
+/// ```
+pub fn point_in_circle(center: [f64; 2], radius: f64) -> Result<[f64; 2], RangeError> {
+  point_in_circle_with(&mut rand::rng(), center, radius)
+}
+
+pub(crate) fn point_in_circle_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  center: [f64; 2],
+  radius: f64,
+) -> Result<[f64; 2], RangeError> {
+  check_radius(radius)?;
+  // The square root compensates for the outer rings holding more area.
+  let r = radius * rng.random::<f64>().sqrt();
+  let (sin, cos) = rng.random_range(0.0..TAU).sin_cos();
+  Ok([center[0] + r * cos, center[1] + r * sin])
+}
+
+/// Generates a point uniformly on the surface of a sphere
+///
+/// # Returns
+///
+/// `[x, y, z]`, or an error if `radius` is negative or not finite
+pub fn point_on_sphere(center: [f64; 3], radius: f64) -> Result<[f64; 3], RangeError> {
+  point_on_sphere_with(&mut rand::rng(), center, radius)
+}
+
+pub(crate) fn point_on_sphere_with<R: RngCore + ?Sized>(
+  rng: &mut R,
+  center: [f64; 3],
+  radius: f64,
+) -> Result<[f64; 3], RangeError> {
+  check_radius(radius)?;
+  let direction: [f64; 3] = unit_vector_with(rng);
+  Ok(std::array::from_fn(|i| center[i] + radius * direction[i]))
+}
+
+fn check_radius(radius: f64) -> Result<(), RangeError> {
+  if !radius.is_finite() {
+    Err(RangeError::NonFinite)
+  } else if radius < 0.0 {
+    Err(RangeError::EmptyRange)
+  } else {
+    Ok(())
+  }
+}
+
+/// Generates a vector of length one pointing in a uniformly random direction
+///
+/// Works in any dimension; a zero-dimensional vector is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::geo::unit_vector;
+///
+/// let [x, y, z] = unit_vector();
+/// assert!(((x * x + y * y + z * z).sqrt() - 1.0).abs() < 1e-9);
+/// ```
+pub fn unit_vector<const N: usize>() -> [f64; N] {
+  unit_vector_with(&mut rand::rng())
+}
+
+pub(crate) fn unit_vector_with<R: RngCore + ?Sized, const N: usize>(rng: &mut R) -> [f64; N] {
+  if N == 0 {
+    return [0.0; N];
+  }
+  // Independent normal coordinates are rotationally symmetric.
+  loop {
+    let v: [f64; N] = std::array::from_fn(|_| StandardNormal.sample(rng));
+    let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm > f64::MIN_POSITIVE {
+      return v.map(|x| x / norm);
+    }
+  }
+}
+
+/// A latitude/longitude rectangle on the globe, in degrees
+///
+/// A box whose west edge lies east of its east edge crosses the antimeridian.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::geo::BoundingBox;
+///
+/// // Around Paris
+/// let paris = BoundingBox::new(48.81, 2.22, 48.91, 2.47).unwrap();
+/// let (lat, lng) = paris.sample();
+/// assert!(paris.contains(lat, lng));
+///
+/// // Fiji, across the antimeridian
+/// let fiji = BoundingBox::new(-21.0, 176.0, -12.0, -178.0).unwrap();
+/// let (lat, lng) = fiji.sample();
+/// assert!(lng >= 176.0 || lng <= -178.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+  south: f64,
+  west: f64,
+  north: f64,
+  east: f64,
+}
+
+impl BoundingBox {
+  /// The whole globe
+  pub const WORLD: Self = Self {
+    south: -90.0,
+    west: -180.0,
+    north: 90.0,
+    east: 180.0,
+  };
+
+  /// Creates a bounding box from its edges
+  ///
+  /// # Errors
+  ///
+  /// - `RangeError::NonFinite` if an edge is not finite
+  /// - `RangeError::EmptyRange` if `south > north`, a latitude is outside
+  ///   `-90..=90` or a longitude is outside `-180..=180`
+  pub fn new(south: f64, west: f64, north: f64, east: f64) -> Result<Self, RangeError> {
+    if [south, west, north, east].iter().any(|x| !x.is_finite()) {
+      return Err(RangeError::NonFinite);
+    }
+    let latitude = -90.0..=90.0;
+    let longitude = -180.0..=180.0;
+    if south > north
+      || !latitude.contains(&south)
+      || !latitude.contains(&north)
+      || !longitude.contains(&west)
+      || !longitude.contains(&east)
+    {
+      return Err(RangeError::EmptyRange);
+    }
+    Ok(Self {
+      south,
+      west,
+      north,
+      east,
+    })
+  }
+
+  /// Whether the coordinates lie within the box
+  pub fn contains(&self, lat: f64, lng: f64) -> bool {
+    let longitude = if self.west <= self.east {
+      (self.west..=self.east).contains(&lng)
+    } else {
+      lng >= self.west || lng <= self.east
+    };
+    (self.south..=self.north).contains(&lat) && longitude
+  }
+
+  /// Draws `(latitude, longitude)` uniformly by surface area with the thread-local generator
+  pub fn sample(&self) -> (f64, f64) {
+    self.sample_with(&mut rand::rng())
+  }
+
+  /// Draws `(latitude, longitude)` with the given generator
+  pub fn sample_with<R: RngCore + ?Sized>(&self, rng: &mut R) -> (f64, f64) {
+    // Area between two latitudes grows with the difference of their sines.
+    let (low, high) = (self.south.to_radians().sin(), self.north.to_radians().sin());
+    let lat = rng
+      .random_range(low..=high)
+      .clamp(-1.0, 1.0)
+      .asin()
+      .to_degrees()
+      .clamp(self.south, self.north);
+    let width = if self.west <= self.east {
+      self.east - self.west
+    } else {
+      self.east - self.west + 360.0
+    };
+    let mut lng = self.west + rng.random_range(0.0..=width);
+    if lng > 180.0 {
+      lng -= 360.0;
+    }
+    (lat, lng)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::rand::geo::{
+    point_in_circle, point_in_rect, point_on_sphere, unit_vector, BoundingBox,
+  };
+  use crate::rand::{RangeError, Rng};
+
+  #[test]
+  fn test_rect_and_circle() {
+    let mut rng = Rng::seeded(1);
+    for _ in 0..1_000 {
+      let [x, y] = rng.point_in_rect(-1.0..1.0, 5.0..=6.0).unwrap();
+      assert!((-1.0..1.0).contains(&x) && (5.0..=6.0).contains(&y));
+    }
+    assert_eq!(
+      point_in_rect(1.0..0.0, 0.0..1.0),
+      Err(RangeError::EmptyRange)
+    );
+
+    // Half of the points of a uniform disc lie within radius / sqrt(2)
+    let inner = (0..20_000)
+      .map(|_| rng.point_in_circle([3.0, -2.0], 2.0).unwrap())
+      .map(|[x, y]| (x - 3.0).hypot(y + 2.0))
+      .inspect(|d| assert!(*d <= 2.0))
+      .filter(|d| *d <= 2.0 / 2f64.sqrt())
+      .count();
+    assert!((9_700..10_300).contains(&inner), "{inner}");
+    assert_eq!(point_in_circle([0.0; 2], 0.0), Ok([0.0; 2]));
+    assert_eq!(point_in_circle([0.0; 2], -1.0), Err(RangeError::EmptyRange));
+    assert_eq!(
+      point_in_circle([0.0; 2], f64::NAN),
+      Err(RangeError::NonFinite)
+    );
+  }
+
+  #[test]
+  fn test_sphere_and_vectors() {
+    let mut rng = Rng::seeded(2);
+    let mut upper = 0;
+    for _ in 0..10_000 {
+      let [x, y, z] = rng.point_on_sphere([1.0, 2.0, 3.0], 4.0).unwrap();
+      let d = ((x - 1.0).powi(2) + (y - 2.0).powi(2) + (z - 3.0).powi(2)).sqrt();
+      assert!((d - 4.0).abs() < 1e-9);
+      upper += usize::from(z > 3.0);
+    }
+    assert!((4_800..5_200).contains(&upper), "{upper}");
+    assert!(point_on_sphere([0.0; 3], -1.0).is_err());
+
+    let v: [f64; 5] = rng.unit_vector();
+    assert!((v.iter().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-9);
+    let [x]: [f64; 1] = unit_vector();
+    assert_eq!(x.abs(), 1.0);
+    assert!(unit_vector::<0>().is_empty());
+  }
+
+  #[test]
+  fn test_bounding_box() {
+    let mut rng = Rng::seeded(3);
+    // Half of the globe's surface lies beyond 30 degrees of latitude
+    let high = (0..30_000)
+      .map(|_| BoundingBox::WORLD.sample_with(&mut rng))
+      .filter(|(lat, _)| lat.abs() > 30.0)
+      .count();
+    assert!((14_500..15_500).contains(&high), "{high}");
+
+    let fiji = BoundingBox::new(-21.0, 176.0, -12.0, -178.0).unwrap();
+    for _ in 0..1_000 {
+      let (lat, lng) = fiji.sample_with(&mut rng);
+      assert!(fiji.contains(lat, lng), "{lat} {lng}");
+      assert!((-180.0..=180.0).contains(&lng));
+    }
+    assert!(!fiji.contains(-15.0, 0.0));
+    assert_eq!(
+      BoundingBox::new(10.0, 0.0, -10.0, 1.0),
+      Err(RangeError::EmptyRange)
+    );
+    assert_eq!(
+      BoundingBox::new(0.0, 0.0, 91.0, 1.0),
+      Err(RangeError::EmptyRange)
+    );
+    assert_eq!(
+      BoundingBox::new(0.0, f64::NAN, 1.0, 1.0),
+      Err(RangeError::NonFinite)
+    );
+  }
+}
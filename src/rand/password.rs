@@ -0,0 +1,423 @@
+//! Password and passphrase generators
+//!
+//! Both generators draw from the operating system's CSPRNG, never from the
+//! seedable [`Rng`](super::Rng), and report the entropy of their output so it
+//! can be checked against a minimum strength.
+
+use std::error::Error;
+use std::fmt;
+
+use rand::seq::SliceRandom;
+use rand::{Rng as _, RngCore};
+
+use super::secure;
+
+mod words;
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+/// Characters easily confused with one another in common fonts
+const AMBIGUOUS: &str = "Il1O0|`'\"";
+
+/// Errors returned when a [`PasswordPolicy`] cannot be satisfied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordError {
+  /// Every character class is excluded
+  EmptyCharset,
+  /// The length is zero or smaller than the number of required classes
+  TooShort,
+  /// Repeats are forbidden and the length exceeds the number of allowed characters
+  TooLong,
+}
+
+impl fmt::Display for PasswordError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PasswordError::EmptyCharset => write!(f, "no character class is allowed"),
+      PasswordError::TooShort => write!(f, "length is too short for the required classes"),
+      PasswordError::TooLong => write!(f, "length exceeds the number of distinct characters"),
+    }
+  }
+}
+
+impl Error for PasswordError {}
+
+/// Whether a character class may or must appear in a password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassRule {
+  /// Never used
+  Exclude,
+  /// May appear
+  Allow,
+  /// Appears at least once
+  Require,
+}
+
+/// Rules for [`PasswordPolicy::generate`]
+///
+/// Passwords are drawn uniformly among all strings that satisfy the policy, so
+/// [`PasswordPolicy::entropy`] is exact rather than an upper bound.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::password::{ClassRule, PasswordPolicy};
+///
+/// let policy = PasswordPolicy {
+///   length: 20,
+///   symbols: ClassRule::Allow,
+///   exclude_ambiguous: true,
+///   ..Default::default()
+/// };
+/// let password = policy.generate().unwrap();
+/// assert_eq!(password.chars().count(), 20);
+/// assert!(policy.entropy().unwrap() > 100.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordPolicy {
+  /// Number of characters
+  pub length: usize,
+  /// `a` to `z`
+  pub lowercase: ClassRule,
+  /// `A` to `Z`
+  pub uppercase: ClassRule,
+  /// `0` to `9`
+  pub digits: ClassRule,
+  /// Printable ASCII punctuation
+  pub symbols: ClassRule,
+  /// Leaves out characters such as `I`, `l`, `1`, `O` and `0`
+  pub exclude_ambiguous: bool,
+  /// Uses every character at most once
+  pub no_repeats: bool,
+}
+
+impl Default for PasswordPolicy {
+  /// 16 characters with at least one of each class
+  fn default() -> Self {
+    Self {
+      length: 16,
+      lowercase: ClassRule::Require,
+      uppercase: ClassRule::Require,
+      digits: ClassRule::Require,
+      symbols: ClassRule::Require,
+      exclude_ambiguous: false,
+      no_repeats: false,
+    }
+  }
+}
+
+impl PasswordPolicy {
+  /// Generates a password with the OS random number generator
+  ///
+  /// # Errors
+  ///
+  /// Returns a [`PasswordError`] if no password can satisfy the policy
+  ///
+  /// # Panics
+  ///
+  /// Panics if the operating system cannot provide random bytes
+  pub fn generate(&self) -> Result<String, PasswordError> {
+    self.generate_with(&mut secure::rng())
+  }
+
+  pub(crate) fn generate_with<R: RngCore + ?Sized>(
+    &self,
+    rng: &mut R,
+  ) -> Result<String, PasswordError> {
+    let (alphabet, required) = self.charset()?;
+    // Rejection sampling keeps the output uniform over all valid passwords.
+    loop {
+      let candidate: Vec<char> = if self.no_repeats {
+        let mut pool = alphabet.clone();
+        pool.partial_shuffle(rng, self.length).0.to_vec()
+      } else {
+        (0..self.length)
+          .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+          .collect()
+      };
+      if required
+        .iter()
+        .all(|class| candidate.iter().any(|c| class.contains(c)))
+      {
+        return Ok(candidate.into_iter().collect());
+      }
+    }
+  }
+
+  /// Entropy of generated passwords in bits
+  ///
+  /// # Errors
+  ///
+  /// Returns a [`PasswordError`] if no password can satisfy the policy
+  pub fn entropy(&self) -> Result<f64, PasswordError> {
+    let (alphabet, required) = self.charset()?;
+    let (n, length) = (alphabet.len(), self.length);
+    // Number of strings without the class requirements, as a base-2 logarithm
+    let all = if self.no_repeats {
+      (0..length).map(|i| ((n - i) as f64).log2()).sum()
+    } else {
+      length as f64 * (n as f64).log2()
+    };
+    // Share of those strings containing every required class, by inclusion-exclusion
+    let mut share = 0.0;
+    for mask in 0..1usize << required.len() {
+      let removed: usize = (0..required.len())
+        .filter(|bit| mask >> bit & 1 == 1)
+        .map(|bit| required[bit].len())
+        .sum();
+      let m = n - removed;
+      let ratio = if self.no_repeats {
+        (0..length)
+          .map(|i| m.saturating_sub(i) as f64 / (n - i) as f64)
+          .product()
+      } else {
+        (m as f64 / n as f64).powi(length as i32)
+      };
+      if mask.count_ones() % 2 == 0 {
+        share += ratio;
+      } else {
+        share -= ratio;
+      }
+    }
+    Ok(all + share.log2())
+  }
+
+  /// Allowed characters and the required classes, after validating the policy
+  fn charset(&self) -> Result<(Vec<char>, Vec<Vec<char>>), PasswordError> {
+    let mut alphabet = Vec::new();
+    let mut required = Vec::new();
+    for (chars, rule) in [
+      (LOWERCASE, self.lowercase),
+      (UPPERCASE, self.uppercase),
+      (DIGITS, self.digits),
+      (SYMBOLS, self.symbols),
+    ] {
+      if rule == ClassRule::Exclude {
+        continue;
+      }
+      let class: Vec<char> = chars
+        .chars()
+        .filter(|c| !self.exclude_ambiguous || !AMBIGUOUS.contains(*c))
+        .collect();
+      alphabet.extend(&class);
+      if rule == ClassRule::Require {
+        required.push(class);
+      }
+    }
+    if alphabet.is_empty() {
+      return Err(PasswordError::EmptyCharset);
+    }
+    if self.length == 0 || self.length < required.len() {
+      return Err(PasswordError::TooShort);
+    }
+    if self.no_repeats && self.length > alphabet.len() {
+      return Err(PasswordError::TooLong);
+    }
+    Ok((alphabet, required))
+  }
+}
+
+/// Diceware-style passphrase generator
+///
+/// Words are drawn independently from an embedded list of about 1500 words
+/// (see [`word_list`]), so every word adds about 10.5 bits of entropy. Six
+/// words are enough for most accounts, eight for keys that must resist
+/// offline attacks.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::password::Passphrase;
+///
+/// let generator = Passphrase { words: 5, ..Default::default() };
+/// let phrase = generator.generate();
+/// assert_eq!(phrase.split('-').count(), 5);
+/// assert!(generator.entropy() > 50.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passphrase {
+  /// Number of words
+  pub words: usize,
+  /// Placed between words; an empty separator may make some phrases ambiguous
+  pub separator: String,
+  /// Capitalizes the first letter of every word
+  pub capitalize: bool,
+}
+
+impl Default for Passphrase {
+  /// Six lowercase words separated by `-`
+  fn default() -> Self {
+    Self {
+      words: 6,
+      separator: "-".to_string(),
+      capitalize: false,
+    }
+  }
+}
+
+impl Passphrase {
+  /// Generates a passphrase with the OS random number generator
+  ///
+  /// # Panics
+  ///
+  /// Panics if the operating system cannot provide random bytes
+  pub fn generate(&self) -> String {
+    self.generate_with(&mut secure::rng())
+  }
+
+  pub(crate) fn generate_with<R: RngCore + ?Sized>(&self, rng: &mut R) -> String {
+    (0..self.words)
+      .map(|_| {
+        let word = words::WORDS[rng.random_range(0..words::WORDS.len())];
+        if self.capitalize {
+          word[..1].to_uppercase() + &word[1..]
+        } else {
+          word.to_string()
+        }
+      })
+      .collect::<Vec<_>>()
+      .join(&self.separator)
+  }
+
+  /// Entropy of generated passphrases in bits
+  pub fn entropy(&self) -> f64 {
+    self.words as f64 * (words::WORDS.len() as f64).log2()
+  }
+}
+
+/// The embedded word list used by [`Passphrase`]
+pub fn word_list() -> &'static [&'static str] {
+  &words::WORDS
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use crate::rand::password::{
+    word_list, ClassRule, Passphrase, PasswordError, PasswordPolicy, AMBIGUOUS,
+  };
+  use crate::rand::Rng;
+
+  #[test]
+  fn test_password_policy() {
+    let password = PasswordPolicy::default().generate().unwrap();
+    assert_eq!(password.len(), 16);
+    assert!(password.chars().any(|c| c.is_ascii_lowercase()));
+    assert!(password.chars().any(|c| c.is_ascii_uppercase()));
+    assert!(password.chars().any(|c| c.is_ascii_digit()));
+    assert!(password.chars().any(|c| c.is_ascii_punctuation()));
+
+    let policy = PasswordPolicy {
+      length: 4,
+      exclude_ambiguous: true,
+      no_repeats: true,
+      ..Default::default()
+    };
+    let mut rng = Rng::seeded(1);
+    for _ in 0..500 {
+      let password = policy.generate_with(&mut rng).unwrap();
+      assert_eq!(password.len(), 4);
+      assert!(!password.chars().any(|c| AMBIGUOUS.contains(c)));
+      assert_eq!(password.chars().collect::<HashSet<_>>().len(), 4);
+      assert!(password.chars().any(|c| c.is_ascii_digit()));
+    }
+
+    let digits_only = PasswordPolicy {
+      length: 6,
+      lowercase: ClassRule::Exclude,
+      uppercase: ClassRule::Exclude,
+      symbols: ClassRule::Exclude,
+      ..Default::default()
+    };
+    let pin = digits_only.generate().unwrap();
+    assert!(pin.chars().all(|c| c.is_ascii_digit()));
+  }
+
+  #[test]
+  fn test_password_errors() {
+    let excluded = PasswordPolicy {
+      lowercase: ClassRule::Exclude,
+      uppercase: ClassRule::Exclude,
+      digits: ClassRule::Exclude,
+      symbols: ClassRule::Exclude,
+      ..Default::default()
+    };
+    assert_eq!(excluded.generate(), Err(PasswordError::EmptyCharset));
+    let short = PasswordPolicy {
+      length: 3,
+      ..Default::default()
+    };
+    assert_eq!(short.generate(), Err(PasswordError::TooShort));
+    let long = PasswordPolicy {
+      length: 11,
+      lowercase: ClassRule::Exclude,
+      uppercase: ClassRule::Exclude,
+      symbols: ClassRule::Exclude,
+      no_repeats: true,
+      ..Default::default()
+    };
+    assert_eq!(long.generate(), Err(PasswordError::TooLong));
+    assert_eq!(long.entropy(), Err(PasswordError::TooLong));
+  }
+
+  #[test]
+  fn test_password_entropy() {
+    let digits = PasswordPolicy {
+      length: 4,
+      lowercase: ClassRule::Exclude,
+      uppercase: ClassRule::Exclude,
+      digits: ClassRule::Allow,
+      symbols: ClassRule::Exclude,
+      ..Default::default()
+    };
+    assert!((digits.entropy().unwrap() - 10_000f64.log2()).abs() < 1e-9);
+
+    let distinct = PasswordPolicy {
+      no_repeats: true,
+      ..digits.clone()
+    };
+    assert!((distinct.entropy().unwrap() - 5_040f64.log2()).abs() < 1e-9);
+
+    // Two characters from {a..z, 0..9} with at least one letter and one digit: 2 * 26 * 10
+    let mixed = PasswordPolicy {
+      length: 2,
+      lowercase: ClassRule::Require,
+      uppercase: ClassRule::Exclude,
+      digits: ClassRule::Require,
+      symbols: ClassRule::Exclude,
+      ..Default::default()
+    };
+    assert!((mixed.entropy().unwrap() - 520f64.log2()).abs() < 1e-9);
+
+    let default = PasswordPolicy::default().entropy().unwrap();
+    assert!(default < 16.0 * 94f64.log2() && default > 100.0);
+  }
+
+  #[test]
+  fn test_passphrase() {
+    let list = word_list();
+    assert_eq!(list.iter().collect::<HashSet<_>>().len(), list.len());
+    assert!(list.windows(2).all(|w| w[0] < w[1]));
+
+    let phrase = Passphrase::default().generate();
+    let words: Vec<&str> = phrase.split('-').collect();
+    assert_eq!(words.len(), 6);
+    assert!(words.iter().all(|w| list.contains(w)));
+
+    let generator = Passphrase {
+      words: 4,
+      separator: " ".to_string(),
+      capitalize: true,
+    };
+    let mut rng = Rng::seeded(2);
+    let phrase = generator.generate_with(&mut rng);
+    assert_eq!(phrase, generator.generate_with(&mut Rng::seeded(2)));
+    assert!(phrase
+      .split(' ')
+      .all(|w| w.chars().next().unwrap().is_ascii_uppercase()));
+    assert!((generator.entropy() - 4.0 * (list.len() as f64).log2()).abs() < 1e-9);
+  }
+}
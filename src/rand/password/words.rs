@@ -0,0 +1,1523 @@
+//! Word list for [`Passphrase`](super::Passphrase)
+//!
+//! Short, common and easy to type English words, sorted and free of duplicates.
+
+pub(super) static WORDS: [&str; 1517] = [
+  "abacus",
+  "able",
+  "acid",
+  "acorn",
+  "acre",
+  "acrobat",
+  "actor",
+  "adapt",
+  "admiral",
+  "admit",
+  "adobe",
+  "adopt",
+  "adult",
+  "aegis",
+  "aerial",
+  "affix",
+  "afford",
+  "agenda",
+  "agent",
+  "agile",
+  "aging",
+  "agree",
+  "ahead",
+  "airport",
+  "aisle",
+  "alarm",
+  "album",
+  "alert",
+  "algae",
+  "alibi",
+  "alien",
+  "align",
+  "alike",
+  "alive",
+  "alley",
+  "allow",
+  "alloy",
+  "aloft",
+  "alone",
+  "alpaca",
+  "alpine",
+  "amber",
+  "amble",
+  "amend",
+  "amid",
+  "ample",
+  "amulet",
+  "amuse",
+  "anagram",
+  "anchor",
+  "angel",
+  "anger",
+  "angle",
+  "ankle",
+  "annex",
+  "antler",
+  "anvil",
+  "apart",
+  "apex",
+  "apple",
+  "apricot",
+  "apron",
+  "aquarium",
+  "arbor",
+  "arcade",
+  "archer",
+  "arena",
+  "argue",
+  "arise",
+  "armor",
+  "aroma",
+  "arrow",
+  "artery",
+  "artisan",
+  "ascot",
+  "ashen",
+  "aside",
+  "aspen",
+  "asset",
+  "asteroid",
+  "atlas",
+  "atom",
+  "atrium",
+  "attic",
+  "audio",
+  "audit",
+  "aurora",
+  "avert",
+  "avid",
+  "avocado",
+  "avoid",
+  "awake",
+  "award",
+  "aware",
+  "awning",
+  "axis",
+  "azure",
+  "backpack",
+  "bacon",
+  "badge",
+  "badger",
+  "bagel",
+  "baker",
+  "balcony",
+  "ballad",
+  "balloon",
+  "balmy",
+  "bamboo",
+  "banana",
+  "banjo",
+  "banner",
+  "barge",
+  "barn",
+  "barrel",
+  "basil",
+  "basin",
+  "basket",
+  "batch",
+  "baton",
+  "bayou",
+  "beach",
+  "beacon",
+  "beard",
+  "beast",
+  "beaver",
+  "bedrock",
+  "beech",
+  "beefy",
+  "beehive",
+  "begin",
+  "being",
+  "bellhop",
+  "belly",
+  "bench",
+  "berry",
+  "bevel",
+  "bicycle",
+  "bike",
+  "binder",
+  "birch",
+  "biscuit",
+  "bison",
+  "blade",
+  "blank",
+  "blanket",
+  "blast",
+  "blaze",
+  "bleak",
+  "blend",
+  "blender",
+  "bless",
+  "blimp",
+  "blink",
+  "bliss",
+  "blizzard",
+  "block",
+  "bloom",
+  "blossom",
+  "blueprint",
+  "blunt",
+  "blush",
+  "board",
+  "boast",
+  "bobcat",
+  "bonfire",
+  "bonus",
+  "bookcase",
+  "boost",
+  "booth",
+  "borax",
+  "botany",
+  "bottle",
+  "boulder",
+  "bounce",
+  "bouquet",
+  "bowl",
+  "boxer",
+  "brace",
+  "bracelet",
+  "braid",
+  "brain",
+  "brake",
+  "brand",
+  "brass",
+  "brave",
+  "bread",
+  "breakfast",
+  "breeze",
+  "brick",
+  "bride",
+  "bridge",
+  "brief",
+  "bright",
+  "brisk",
+  "broad",
+  "broom",
+  "brown",
+  "brownie",
+  "brush",
+  "bubble",
+  "bucket",
+  "buckle",
+  "buckwheat",
+  "budget",
+  "buffalo",
+  "bugle",
+  "build",
+  "bulb",
+  "bulldozer",
+  "bundle",
+  "bungalow",
+  "bunny",
+  "burlap",
+  "burrito",
+  "burst",
+  "bush",
+  "butter",
+  "butterfly",
+  "button",
+  "buzzer",
+  "cabbage",
+  "cabin",
+  "cable",
+  "caboose",
+  "cactus",
+  "cadet",
+  "cake",
+  "calendar",
+  "calm",
+  "camel",
+  "cameo",
+  "camera",
+  "campfire",
+  "canal",
+  "candle",
+  "candy",
+  "cannon",
+  "canoe",
+  "canopy",
+  "canvas",
+  "canyon",
+  "caper",
+  "caramel",
+  "carbon",
+  "cardigan",
+  "cargo",
+  "carnival",
+  "carol",
+  "carpet",
+  "carrot",
+  "carve",
+  "cascade",
+  "cashew",
+  "castle",
+  "casual",
+  "catalog",
+  "cathedral",
+  "cauldron",
+  "caviar",
+  "cedar",
+  "celery",
+  "cello",
+  "cement",
+  "census",
+  "ceramic",
+  "chalk",
+  "chamber",
+  "champ",
+  "chant",
+  "chapel",
+  "chariot",
+  "charm",
+  "chart",
+  "chase",
+  "cheek",
+  "cheer",
+  "cheese",
+  "cheetah",
+  "cherry",
+  "chess",
+  "chest",
+  "chestnut",
+  "chief",
+  "chime",
+  "chimney",
+  "chipmunk",
+  "chipper",
+  "chorus",
+  "chowder",
+  "cider",
+  "cinema",
+  "cinnamon",
+  "circle",
+  "citrus",
+  "civic",
+  "clamp",
+  "clang",
+  "clarinet",
+  "clay",
+  "clean",
+  "clerk",
+  "click",
+  "cliff",
+  "climb",
+  "clinic",
+  "cloak",
+  "clock",
+  "cloth",
+  "cloud",
+  "clover",
+  "clown",
+  "coach",
+  "coast",
+  "coaster",
+  "cobalt",
+  "cobbler",
+  "cocoa",
+  "coconut",
+  "coffee",
+  "collar",
+  "comet",
+  "comfy",
+  "comma",
+  "compass",
+  "compost",
+  "concert",
+  "condor",
+  "copper",
+  "coral",
+  "cork",
+  "corner",
+  "cornet",
+  "corridor",
+  "cosmic",
+  "costume",
+  "cottage",
+  "cotton",
+  "couch",
+  "cougar",
+  "cousin",
+  "cove",
+  "cowboy",
+  "coyote",
+  "crab",
+  "cradle",
+  "craft",
+  "cranberry",
+  "crane",
+  "crater",
+  "crayon",
+  "cream",
+  "creek",
+  "crescent",
+  "crest",
+  "cricket",
+  "crisp",
+  "crocodile",
+  "croquet",
+  "crossword",
+  "crown",
+  "crumb",
+  "crust",
+  "crystal",
+  "cube",
+  "cucumber",
+  "cuddle",
+  "cupboard",
+  "cupcake",
+  "curly",
+  "curve",
+  "cushion",
+  "cycle",
+  "cymbal",
+  "cypress",
+  "daisy",
+  "dance",
+  "dandelion",
+  "dapper",
+  "dash",
+  "dawn",
+  "daybreak",
+  "debut",
+  "decal",
+  "decimal",
+  "decoy",
+  "deed",
+  "deer",
+  "delight",
+  "delta",
+  "denim",
+  "dentist",
+  "depot",
+  "depth",
+  "desert",
+  "design",
+  "desk",
+  "detour",
+  "dial",
+  "diamond",
+  "diary",
+  "diesel",
+  "digit",
+  "dime",
+  "diner",
+  "dingo",
+  "dinner",
+  "dinosaur",
+  "dipper",
+  "direct",
+  "disco",
+  "dish",
+  "ditto",
+  "diver",
+  "dizzy",
+  "dock",
+  "dodge",
+  "dolphin",
+  "domain",
+  "donut",
+  "doorbell",
+  "doughnut",
+  "dove",
+  "draft",
+  "dragon",
+  "dragonfly",
+  "drama",
+  "drape",
+  "dream",
+  "dress",
+  "drift",
+  "drill",
+  "drink",
+  "drive",
+  "drum",
+  "duck",
+  "dumpling",
+  "dune",
+  "dusk",
+  "dust",
+  "dwarf",
+  "dynamo",
+  "eager",
+  "eagle",
+  "early",
+  "earth",
+  "easel",
+  "east",
+  "easter",
+  "ebony",
+  "echo",
+  "eclipse",
+  "edge",
+  "edit",
+  "eel",
+  "effort",
+  "eggplant",
+  "eight",
+  "elbow",
+  "elder",
+  "elect",
+  "elegant",
+  "elephant",
+  "elevator",
+  "elk",
+  "elm",
+  "ember",
+  "emblem",
+  "emerald",
+  "empty",
+  "enamel",
+  "endless",
+  "energy",
+  "engine",
+  "enjoy",
+  "entry",
+  "envelope",
+  "envoy",
+  "epic",
+  "equal",
+  "equip",
+  "erase",
+  "errand",
+  "escape",
+  "espresso",
+  "essay",
+  "ether",
+  "evergreen",
+  "evoke",
+  "exact",
+  "exam",
+  "excel",
+  "exile",
+  "exit",
+  "expert",
+  "extra",
+  "fabric",
+  "facet",
+  "fairway",
+  "fairy",
+  "faith",
+  "falafel",
+  "falcon",
+  "fame",
+  "fancy",
+  "fang",
+  "farm",
+  "fauna",
+  "feast",
+  "feather",
+  "fedora",
+  "fence",
+  "fern",
+  "ferret",
+  "ferry",
+  "festival",
+  "fetch",
+  "fiber",
+  "fiddle",
+  "field",
+  "fiesta",
+  "figure",
+  "filter",
+  "finch",
+  "finger",
+  "firefly",
+  "fireplace",
+  "fjord",
+  "flag",
+  "flame",
+  "flamingo",
+  "flannel",
+  "flash",
+  "flashlight",
+  "flask",
+  "fleet",
+  "flint",
+  "flipper",
+  "flock",
+  "flora",
+  "flour",
+  "flute",
+  "focus",
+  "foggy",
+  "folder",
+  "football",
+  "footprint",
+  "forest",
+  "forge",
+  "fork",
+  "fossil",
+  "fountain",
+  "fox",
+  "fragile",
+  "frame",
+  "freckle",
+  "freezer",
+  "fresh",
+  "frisbee",
+  "frost",
+  "fruit",
+  "fudge",
+  "funnel",
+  "furnace",
+  "fuzzy",
+  "gadget",
+  "galaxy",
+  "gallery",
+  "gallon",
+  "game",
+  "garden",
+  "gardener",
+  "garlic",
+  "garnet",
+  "gauge",
+  "gazebo",
+  "gazelle",
+  "gecko",
+  "gem",
+  "gemstone",
+  "genius",
+  "gentle",
+  "geyser",
+  "ghost",
+  "giant",
+  "ginger",
+  "gingham",
+  "giraffe",
+  "glacier",
+  "glade",
+  "glass",
+  "glide",
+  "glitter",
+  "globe",
+  "glory",
+  "glove",
+  "glow",
+  "glue",
+  "gnome",
+  "goblet",
+  "goggles",
+  "golden",
+  "goldfish",
+  "gondola",
+  "gopher",
+  "gorilla",
+  "gospel",
+  "gown",
+  "grace",
+  "grain",
+  "granite",
+  "grape",
+  "graph",
+  "grass",
+  "gravel",
+  "gravity",
+  "gravy",
+  "green",
+  "grid",
+  "griffin",
+  "grill",
+  "grin",
+  "groove",
+  "grove",
+  "guacamole",
+  "guava",
+  "guest",
+  "guide",
+  "guitar",
+  "gull",
+  "gulp",
+  "gumdrop",
+  "gust",
+  "habit",
+  "hairpin",
+  "halibut",
+  "hallway",
+  "hammer",
+  "hammock",
+  "hamper",
+  "hamster",
+  "handbag",
+  "handy",
+  "harbor",
+  "harmonica",
+  "harmony",
+  "harp",
+  "harvest",
+  "hatch",
+  "haven",
+  "hawk",
+  "hazel",
+  "hazelnut",
+  "headlamp",
+  "heart",
+  "hearth",
+  "hedge",
+  "hedgehog",
+  "helium",
+  "helmet",
+  "herald",
+  "herb",
+  "hermit",
+  "heron",
+  "hickory",
+  "highway",
+  "hiking",
+  "hill",
+  "hilltop",
+  "hippo",
+  "hobby",
+  "hockey",
+  "holly",
+  "honey",
+  "hoop",
+  "horizon",
+  "hornet",
+  "horseshoe",
+  "hotdog",
+  "hotel",
+  "hound",
+  "humble",
+  "hummus",
+  "hurdle",
+  "hurricane",
+  "husky",
+  "hybrid",
+  "hymn",
+  "iceberg",
+  "icebox",
+  "icicle",
+  "icon",
+  "idea",
+  "igloo",
+  "iguana",
+  "image",
+  "impact",
+  "inch",
+  "index",
+  "indigo",
+  "infant",
+  "ink",
+  "inkwell",
+  "inlet",
+  "input",
+  "insect",
+  "inspire",
+  "intact",
+  "invent",
+  "iris",
+  "iron",
+  "island",
+  "issue",
+  "ivory",
+  "jackal",
+  "jacket",
+  "jaguar",
+  "jam",
+  "jamboree",
+  "jargon",
+  "jasmine",
+  "jazz",
+  "jelly",
+  "jellybean",
+  "jersey",
+  "jewel",
+  "jigsaw",
+  "jockey",
+  "jogger",
+  "joint",
+  "jolly",
+  "journal",
+  "journey",
+  "joy",
+  "judge",
+  "juggle",
+  "juice",
+  "jukebox",
+  "jumbo",
+  "jungle",
+  "juniper",
+  "jury",
+  "kale",
+  "kangaroo",
+  "kayak",
+  "kebab",
+  "keen",
+  "kennel",
+  "kettle",
+  "keyboard",
+  "keynote",
+  "kickstand",
+  "kidney",
+  "kiln",
+  "kimono",
+  "kind",
+  "kingdom",
+  "kingfisher",
+  "kiosk",
+  "kite",
+  "kitten",
+  "kiwi",
+  "knack",
+  "knee",
+  "knight",
+  "knob",
+  "knot",
+  "koala",
+  "label",
+  "lace",
+  "ladder",
+  "ladybug",
+  "lagoon",
+  "lake",
+  "lamp",
+  "lamppost",
+  "landmark",
+  "lantern",
+  "laptop",
+  "large",
+  "lasagna",
+  "laser",
+  "latch",
+  "lava",
+  "lavender",
+  "lawn",
+  "layer",
+  "leaf",
+  "league",
+  "ledge",
+  "legend",
+  "lemon",
+  "lemonade",
+  "lens",
+  "leopard",
+  "letter",
+  "level",
+  "lever",
+  "library",
+  "lighthouse",
+  "lilac",
+  "lily",
+  "limber",
+  "lime",
+  "limerick",
+  "linen",
+  "lion",
+  "liquid",
+  "little",
+  "lizard",
+  "llama",
+  "lobby",
+  "lobster",
+  "locket",
+  "lodge",
+  "lofty",
+  "logic",
+  "lollipop",
+  "lotus",
+  "lounge",
+  "loyal",
+  "lucky",
+  "luggage",
+  "lullaby",
+  "lumber",
+  "lunar",
+  "lunch",
+  "lyric",
+  "macaroni",
+  "machine",
+  "magician",
+  "magnet",
+  "magpie",
+  "mailbox",
+  "maize",
+  "mallet",
+  "mandolin",
+  "mango",
+  "manor",
+  "maple",
+  "marathon",
+  "marble",
+  "march",
+  "margin",
+  "marigold",
+  "marina",
+  "market",
+  "marmalade",
+  "marsh",
+  "mascot",
+  "mattress",
+  "meadow",
+  "meatball",
+  "medal",
+  "megaphone",
+  "melody",
+  "melon",
+  "memo",
+  "mentor",
+  "menu",
+  "mercy",
+  "merit",
+  "mermaid",
+  "mesa",
+  "metal",
+  "meteor",
+  "method",
+  "microwave",
+  "midst",
+  "mighty",
+  "mild",
+  "milestone",
+  "milkshake",
+  "mill",
+  "mimic",
+  "mineral",
+  "minivan",
+  "minnow",
+  "mint",
+  "mirror",
+  "mistletoe",
+  "mitten",
+  "mixer",
+  "moat",
+  "mocha",
+  "model",
+  "modern",
+  "molar",
+  "molasses",
+  "monarch",
+  "monkey",
+  "monsoon",
+  "moonbeam",
+  "moose",
+  "moped",
+  "morning",
+  "mosaic",
+  "mosquito",
+  "moss",
+  "motel",
+  "motor",
+  "mound",
+  "mountain",
+  "mouse",
+  "muffin",
+  "mural",
+  "museum",
+  "mushroom",
+  "music",
+  "mustang",
+  "mustard",
+  "myth",
+  "nacho",
+  "napkin",
+  "narrow",
+  "native",
+  "nature",
+  "navy",
+  "nebula",
+  "necklace",
+  "nectar",
+  "needle",
+  "neon",
+  "nephew",
+  "nest",
+  "nickel",
+  "night",
+  "nightcap",
+  "nimble",
+  "noble",
+  "nomad",
+  "noodle",
+  "normal",
+  "north",
+  "notch",
+  "notebook",
+  "novel",
+  "nudge",
+  "nugget",
+  "number",
+  "nutmeg",
+  "nutshell",
+  "nylon",
+  "oak",
+  "oasis",
+  "oath",
+  "oatmeal",
+  "ocean",
+  "octagon",
+  "octave",
+  "octopus",
+  "odor",
+  "office",
+  "olive",
+  "omega",
+  "omelet",
+  "onion",
+  "onset",
+  "opal",
+  "open",
+  "opera",
+  "orange",
+  "orbit",
+  "orbital",
+  "orchard",
+  "orchid",
+  "organ",
+  "origin",
+  "ostrich",
+  "otter",
+  "ounce",
+  "outfit",
+  "outpost",
+  "oval",
+  "oven",
+  "overcoat",
+  "owl",
+  "oxygen",
+  "oyster",
+  "paddle",
+  "pagoda",
+  "paintbrush",
+  "palace",
+  "palm",
+  "pancake",
+  "panda",
+  "panel",
+  "panther",
+  "papaya",
+  "paprika",
+  "parachute",
+  "parade",
+  "parcel",
+  "park",
+  "parrot",
+  "parsley",
+  "passport",
+  "pasta",
+  "pastel",
+  "patch",
+  "path",
+  "patio",
+  "peach",
+  "peacock",
+  "peanut",
+  "pearl",
+  "pebble",
+  "pecan",
+  "pedal",
+  "pegasus",
+  "pelican",
+  "pencil",
+  "pendulum",
+  "penguin",
+  "pepper",
+  "peppermint",
+  "perch",
+  "permit",
+  "pewter",
+  "pharaoh",
+  "pheasant",
+  "photo",
+  "piano",
+  "pickle",
+  "picnic",
+  "pilot",
+  "pine",
+  "pinecone",
+  "pinwheel",
+  "pioneer",
+  "pirate",
+  "pistachio",
+  "pitchfork",
+  "pixel",
+  "pizza",
+  "plain",
+  "planet",
+  "plank",
+  "planter",
+  "platypus",
+  "playground",
+  "plaza",
+  "plum",
+  "plumber",
+  "plush",
+  "pocket",
+  "poem",
+  "polar",
+  "polka",
+  "pond",
+  "pony",
+  "poppy",
+  "porch",
+  "porcupine",
+  "portal",
+  "postcard",
+  "potato",
+  "pottery",
+  "powder",
+  "prairie",
+  "pretzel",
+  "printer",
+  "prism",
+  "prize",
+  "prose",
+  "proud",
+  "pudding",
+  "pueblo",
+  "puffin",
+  "pulley",
+  "pulse",
+  "puma",
+  "pumpkin",
+  "puppet",
+  "puzzle",
+  "pyramid",
+  "quail",
+  "quaint",
+  "quake",
+  "quarry",
+  "quartet",
+  "quartz",
+  "quesadilla",
+  "quest",
+  "quick",
+  "quiet",
+  "quill",
+  "quilt",
+  "quiver",
+  "quota",
+  "rabbit",
+  "raccoon",
+  "racecar",
+  "radar",
+  "radio",
+  "radish",
+  "raft",
+  "rail",
+  "railroad",
+  "rain",
+  "rainbow",
+  "raindrop",
+  "raisin",
+  "rally",
+  "ramp",
+  "ranch",
+  "range",
+  "rapid",
+  "rattle",
+  "raven",
+  "razor",
+  "reach",
+  "ready",
+  "recipe",
+  "recital",
+  "record",
+  "reef",
+  "refuge",
+  "region",
+  "reindeer",
+  "relay",
+  "relic",
+  "remedy",
+  "rescue",
+  "ribbon",
+  "rice",
+  "ridge",
+  "rifle",
+  "ripple",
+  "river",
+  "riverbed",
+  "roast",
+  "robin",
+  "robot",
+  "rocket",
+  "rodeo",
+  "roof",
+  "rookie",
+  "rose",
+  "rosemary",
+  "rover",
+  "rowboat",
+  "royal",
+  "ruby",
+  "rudder",
+  "rugby",
+  "ruler",
+  "rumble",
+  "runway",
+  "rustic",
+  "saddle",
+  "safari",
+  "saffron",
+  "saga",
+  "sage",
+  "sail",
+  "sailboat",
+  "salad",
+  "salmon",
+  "salsa",
+  "salute",
+  "sample",
+  "sandal",
+  "sandbox",
+  "sapling",
+  "sapphire",
+  "sardine",
+  "satin",
+  "sauce",
+  "sauna",
+  "savanna",
+  "saxophone",
+  "scale",
+  "scallop",
+  "scarecrow",
+  "scarf",
+  "scene",
+  "scholar",
+  "school",
+  "scooter",
+  "scout",
+  "scroll",
+  "sculpt",
+  "seahorse",
+  "seal",
+  "seashell",
+  "season",
+  "second",
+  "secret",
+  "sector",
+  "sedan",
+  "seed",
+  "semester",
+  "sequel",
+  "sesame",
+  "shadow",
+  "shale",
+  "shamrock",
+  "shark",
+  "shell",
+  "shelter",
+  "sheriff",
+  "sherpa",
+  "shield",
+  "shine",
+  "ship",
+  "shipyard",
+  "shore",
+  "shovel",
+  "shrimp",
+  "sidewalk",
+  "siesta",
+  "signal",
+  "silent",
+  "silk",
+  "silver",
+  "simple",
+  "siren",
+  "sketch",
+  "skiff",
+  "skill",
+  "skillet",
+  "skunk",
+  "skylight",
+  "skyline",
+  "sled",
+  "sleek",
+  "sleeve",
+  "slice",
+  "slipper",
+  "slope",
+  "smile",
+  "smooth",
+  "snack",
+  "snail",
+  "snorkel",
+  "snow",
+  "snowball",
+  "snowflake",
+  "snowman",
+  "soap",
+  "soapbox",
+  "soccer",
+  "socket",
+  "sofa",
+  "solar",
+  "solid",
+  "sombrero",
+  "sonar",
+  "sonnet",
+  "spark",
+  "sparrow",
+  "spatula",
+  "spear",
+  "spice",
+  "spider",
+  "spinach",
+  "spiral",
+  "splash",
+  "sponge",
+  "spoon",
+  "sport",
+  "spring",
+  "sprinkle",
+  "sprout",
+  "spruce",
+  "squash",
+  "squid",
+  "squirrel",
+  "stable",
+  "stadium",
+  "stage",
+  "stallion",
+  "stamp",
+  "star",
+  "starfish",
+  "statue",
+  "steam",
+  "steel",
+  "stellar",
+  "stereo",
+  "stew",
+  "stingray",
+  "stone",
+  "stool",
+  "storm",
+  "story",
+  "stove",
+  "straw",
+  "strawberry",
+  "stream",
+  "stripe",
+  "studio",
+  "sturdy",
+  "submarine",
+  "sugar",
+  "suite",
+  "summit",
+  "sunbeam",
+  "sundial",
+  "sunflower",
+  "sunny",
+  "sunset",
+  "surf",
+  "surfboard",
+  "sushi",
+  "swamp",
+  "swan",
+  "sweater",
+  "swift",
+  "switch",
+  "symbol",
+  "syrup",
+  "table",
+  "tablet",
+  "taco",
+  "tadpole",
+  "talent",
+  "tambourine",
+  "tangerine",
+  "tango",
+  "tank",
+  "tapir",
+  "target",
+  "tattoo",
+  "taxi",
+  "teacup",
+  "teapot",
+  "teaspoon",
+  "telescope",
+  "tempo",
+  "tender",
+  "tennis",
+  "tent",
+  "terrace",
+  "thicket",
+  "thimble",
+  "thistle",
+  "thread",
+  "thrive",
+  "thunder",
+  "ticket",
+  "tiger",
+  "timber",
+  "tinsel",
+  "toast",
+  "toaster",
+  "toboggan",
+  "toffee",
+  "tofu",
+  "tomato",
+  "tonic",
+  "toolbox",
+  "toothbrush",
+  "topaz",
+  "topsoil",
+  "torch",
+  "tornado",
+  "tortoise",
+  "totem",
+  "toucan",
+  "tower",
+  "toy",
+  "track",
+  "tractor",
+  "trail",
+  "train",
+  "trampoline",
+  "travel",
+  "treaty",
+  "treehouse",
+  "trek",
+  "trellis",
+  "tribe",
+  "tricycle",
+  "trident",
+  "trombone",
+  "trophy",
+  "trout",
+  "truck",
+  "trumpet",
+  "trunk",
+  "tugboat",
+  "tulip",
+  "tuna",
+  "tundra",
+  "tunnel",
+  "turban",
+  "turkey",
+  "turnip",
+  "turquoise",
+  "turtle",
+  "tuxedo",
+  "twig",
+  "twin",
+  "typhoon",
+  "ukulele",
+  "ultra",
+  "umber",
+  "umbrella",
+  "umpire",
+  "uncle",
+  "unicorn",
+  "unicycle",
+  "union",
+  "unit",
+  "unity",
+  "upbeat",
+  "uphill",
+  "upper",
+  "upstairs",
+  "uranium",
+  "urban",
+  "usher",
+  "utmost",
+  "utopia",
+  "vaccine",
+  "vacuum",
+  "valentine",
+  "valley",
+  "valve",
+  "vanilla",
+  "vapor",
+  "velvet",
+  "vendor",
+  "venom",
+  "venture",
+  "venus",
+  "verb",
+  "verse",
+  "vessel",
+  "vest",
+  "viking",
+  "villa",
+  "vine",
+  "vineyard",
+  "vinyl",
+  "violet",
+  "violin",
+  "viper",
+  "visit",
+  "vista",
+  "vital",
+  "vivid",
+  "vocal",
+  "volcano",
+  "volleyball",
+  "voyage",
+  "vulture",
+  "waffle",
+  "wagon",
+  "walnut",
+  "walrus",
+  "wander",
+  "wasabi",
+  "water",
+  "waterfall",
+  "watermelon",
+  "wave",
+  "wealth",
+  "weasel",
+  "weaver",
+  "wedge",
+  "weekend",
+  "wharf",
+  "wheat",
+  "wheel",
+  "whirlpool",
+  "whisk",
+  "whistle",
+  "widget",
+  "wildcat",
+  "willow",
+  "windmill",
+  "window",
+  "winter",
+  "wisdom",
+  "wishbone",
+  "wizard",
+  "wombat",
+  "wonder",
+  "wood",
+  "woodpecker",
+  "woolen",
+  "world",
+  "wren",
+  "wrench",
+  "wrist",
+  "wristband",
+  "xenon",
+  "xylophone",
+  "yacht",
+  "yak",
+  "yard",
+  "yarn",
+  "yearbook",
+  "yeast",
+  "yellow",
+  "yeti",
+  "yodel",
+  "yoga",
+  "yogurt",
+  "yolk",
+  "young",
+  "yucca",
+  "yummy",
+  "zebra",
+  "zenith",
+  "zephyr",
+  "zero",
+  "zesty",
+  "zigzag",
+  "zinc",
+  "zipper",
+  "zodiac",
+  "zombie",
+  "zone",
+  "zoom",
+];
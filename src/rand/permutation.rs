@@ -0,0 +1,257 @@
+//! Partial shuffles, derangements and keyed permutations
+//!
+//! [`partial_shuffle`] and [`derangement`] use the thread-local generator and
+//! are also available as methods of [`Rng`](super::Rng). [`Permutation`] needs
+//! no generator at all: it is fully determined by its key.
+
+use rand::{seq::SliceRandom, RngCore};
+
+/// Moves `k` randomly chosen elements to the end of the slice, in random order
+///
+/// Only `k` swaps are made, so picking a random top-k of a large slice is much
+/// cheaper than a full [`shuffle`](super::shuffle).
+///
+/// # Returns
+///
+/// The last `k` elements of the slice, chosen by
+/// [`rand::seq::SliceRandom::partial_shuffle`], or the whole slice shuffled if
+/// `k` exceeds its length
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::permutation::partial_shuffle;
+///
+/// let mut entries: Vec<u32> = (1..=1000).collect();
+/// let winners = partial_shuffle(&mut entries, 3);
+/// assert_eq!(winners.len(), 3);
+/// ```
+pub fn partial_shuffle<T>(slice: &mut [T], k: usize) -> &mut [T] {
+  partial_shuffle_with(&mut rand::rng(), slice, k)
+}
+
+pub(crate) fn partial_shuffle_with<'a, T, R: RngCore + ?Sized>(
+  rng: &mut R,
+  slice: &'a mut [T],
+  k: usize,
+) -> &'a mut [T] {
+  slice.partial_shuffle(rng, k).0
+}
+
+/// Generates a random permutation of `0..n` where no index stays in place
+///
+/// Every derangement is equally likely. Uses rejection sampling, which needs
+/// about e ≈ 2.7 shuffles on average.
+///
+/// # Returns
+///
+/// `Some(p)` with `p[i] != i` for every `i`, or `None` for `n == 1` since a
+/// single element cannot move
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::permutation::derangement;
+///
+/// let names = ["ann", "bob", "cid", "dee"];
+/// let receivers = derangement(names.len()).unwrap();
+/// for (giver, receiver) in names.iter().zip(&receivers) {
+///   assert_ne!(*giver, names[*receiver]);
+///   println!("{giver} gives a present to {}", names[*receiver]);
+/// }
+/// ```
+pub fn derangement(n: usize) -> Option<Vec<usize>> {
+  derangement_with(&mut rand::rng(), n)
+}
+
+pub(crate) fn derangement_with<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> Option<Vec<usize>> {
+  if n == 1 {
+    return None;
+  }
+  let mut p: Vec<usize> = (0..n).collect();
+  loop {
+    p.shuffle(rng);
+    if p.iter().enumerate().all(|(i, &j)| i != j) {
+      return Some(p);
+    }
+  }
+}
+
+/// Number of Feistel rounds, as in the FF1 format-preserving encryption mode
+const ROUNDS: u8 = 10;
+
+/// A keyed, reversible permutation of `0..size`
+///
+/// Maps every index to a distinct index of the same range without storing a
+/// table, using a balanced Feistel network keyed with BLAKE3 and cycle
+/// walking to stay inside the domain. The mapping depends only on the key and
+/// the size, so sequential database ids can be turned into unguessable public
+/// ids and back.
+///
+/// This hides the order of ids from casual observers; it is not a substitute
+/// for authorization checks.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::permutation::Permutation;
+///
+/// let ids = Permutation::new(1_000_000, b"server secret");
+/// let public = ids.apply(42).unwrap();
+/// assert!(public < 1_000_000);
+/// assert_eq!(ids.invert(public), Some(42));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permutation {
+  size: u64,
+  half_bits: u32,
+  key: [u8; 32],
+}
+
+impl Permutation {
+  /// Creates the permutation of `0..size` selected by `key`
+  pub fn new(size: u64, key: impl AsRef<[u8]>) -> Self {
+    let bits = (u64::BITS - size.saturating_sub(1).leading_zeros()).max(2);
+    Self {
+      size,
+      half_bits: bits.div_ceil(2),
+      key: blake3::derive_key("helpers.rand.permutation.v1", key.as_ref()),
+    }
+  }
+
+  /// Number of indices in the domain
+  pub fn size(&self) -> u64 {
+    self.size
+  }
+
+  /// Maps `index` to its image, or `None` if it is not below [`size`](Self::size)
+  pub fn apply(&self, index: u64) -> Option<u64> {
+    self.walk(index, |x| self.encrypt(x))
+  }
+
+  /// Maps an image back to its index, or `None` if it is not below [`size`](Self::size)
+  pub fn invert(&self, image: u64) -> Option<u64> {
+    self.walk(image, |x| self.decrypt(x))
+  }
+
+  /// Applies `step` until the value falls back inside the domain
+  ///
+  /// The network permutes `0..4^half_bits`, which is less than four times the
+  /// domain, so this takes fewer than four steps on average.
+  fn walk(&self, mut x: u64, step: impl Fn(u64) -> u64) -> Option<u64> {
+    if x >= self.size {
+      return None;
+    }
+    loop {
+      x = step(x);
+      if x < self.size {
+        return Some(x);
+      }
+    }
+  }
+
+  fn mask(&self) -> u64 {
+    (1u64 << self.half_bits) - 1
+  }
+
+  fn encrypt(&self, x: u64) -> u64 {
+    let (mut left, mut right) = (x >> self.half_bits, x & self.mask());
+    for round in 0..ROUNDS {
+      (left, right) = (right, left ^ self.round(round, right));
+    }
+    left << self.half_bits | right
+  }
+
+  fn decrypt(&self, x: u64) -> u64 {
+    let (mut left, mut right) = (x >> self.half_bits, x & self.mask());
+    for round in (0..ROUNDS).rev() {
+      (left, right) = (right ^ self.round(round, left), left);
+    }
+    left << self.half_bits | right
+  }
+
+  fn round(&self, round: u8, half: u64) -> u64 {
+    let mut hasher = blake3::Hasher::new_keyed(&self.key);
+    hasher.update(&[round]);
+    hasher.update(&half.to_le_bytes());
+    let digest = hasher.finalize();
+    let bytes = digest.as_bytes()[..8]
+      .try_into()
+      .expect("digest is 32 bytes");
+    u64::from_le_bytes(bytes) & self.mask()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use crate::rand::permutation::{derangement, partial_shuffle, Permutation};
+  use crate::rand::Rng;
+
+  #[test]
+  fn test_partial_shuffle() {
+    let mut items: Vec<u32> = (0..100).collect();
+    assert_eq!(partial_shuffle(&mut items, 10).len(), 10);
+    let mut sorted = items.clone();
+    sorted.sort();
+    assert_eq!(sorted, (0..100).collect::<Vec<_>>());
+    assert_eq!(partial_shuffle(&mut items, 500).len(), 100);
+    assert!(partial_shuffle::<u8>(&mut [], 3).is_empty());
+
+    // Each element should be chosen about equally often
+    let mut rng = Rng::seeded(1);
+    let mut counts = [0usize; 5];
+    for _ in 0..50_000 {
+      let mut items = [0, 1, 2, 3, 4];
+      counts[rng.partial_shuffle(&mut items, 1)[0]] += 1;
+    }
+    assert!(
+      counts.iter().all(|&c| (9_500..10_500).contains(&c)),
+      "{counts:?}"
+    );
+  }
+
+  #[test]
+  fn test_derangement() {
+    assert_eq!(derangement(0), Some(vec![]));
+    assert_eq!(derangement(1), None);
+    assert_eq!(derangement(2), Some(vec![1, 0]));
+    for n in 3..20 {
+      let p = derangement(n).unwrap();
+      assert!(p.iter().enumerate().all(|(i, &j)| i != j));
+      assert_eq!(p.iter().collect::<HashSet<_>>().len(), n);
+    }
+    // The two 3-cycles are the only derangements of three elements
+    let mut rng = Rng::seeded(2);
+    let cycles = (0..10_000)
+      .filter(|_| rng.derangement(3) == Some(vec![1, 2, 0]))
+      .count();
+    assert!((4_700..5_300).contains(&cycles), "{cycles}");
+  }
+
+  #[test]
+  fn test_permutation() {
+    for size in [1u64, 2, 3, 10, 1000, 4097] {
+      let p = Permutation::new(size, b"key");
+      let images: HashSet<u64> = (0..size).map(|i| p.apply(i).unwrap()).collect();
+      assert_eq!(images.len() as u64, size);
+      assert!(images.iter().all(|&y| y < size));
+      for i in 0..size {
+        assert_eq!(p.invert(p.apply(i).unwrap()), Some(i));
+      }
+      assert_eq!(p.apply(size), None);
+      assert_eq!(p.invert(size), None);
+    }
+    let a = Permutation::new(1 << 20, b"one");
+    let b = Permutation::new(1 << 20, b"two");
+    let same = (0..1000).filter(|&i| a.apply(i) == b.apply(i)).count();
+    assert!(same < 5);
+    assert_eq!(a.apply(7), Permutation::new(1 << 20, b"one").apply(7));
+    assert_eq!(Permutation::new(0, b"key").apply(0), None);
+
+    let full = Permutation::new(u64::MAX, b"key");
+    let x = full.apply(u64::MAX - 2).unwrap();
+    assert_eq!(full.invert(x), Some(u64::MAX - 2));
+  }
+}
@@ -0,0 +1,373 @@
+//! Random instances of whole types
+//!
+//! [`Random`] is implemented for primitives, strings and common containers,
+//! and can be derived for your own structs and enums with the `derive`
+//! feature, which is handy for property tests and fixtures.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use helpers::rand::{Random, Rng};
+//!
+//! #[derive(Debug, Random)]
+//! enum Plan {
+//!   #[random(weight = 9)]
+//!   Free,
+//!   Pro { seats: u8 },
+//! }
+//!
+//! #[derive(Debug, Random)]
+//! struct Customer {
+//!   #[random(range = 18..=99)]
+//!   age: u8,
+//!   #[random(len = 5..=12)]
+//!   nickname: String,
+//!   #[random(choose = ["fr", "de", "en"])]
+//!   language: String,
+//!   #[random(with = score)]
+//!   score: f64,
+//!   plan: Plan,
+//!   tags: Vec<u16>,
+//! }
+//!
+//! fn score(rng: &mut Rng) -> f64 {
+//!   rng.normal(50.0, 10.0).unwrap()
+//! }
+//!
+//! let customer = Customer::random_with(&mut Rng::seeded(1));
+//! assert!((18..=99).contains(&customer.age));
+//! # }
+//! ```
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Rng;
+
+/// Largest length of the strings and collections generated by [`Random`]
+pub const MAX_LEN: usize = 8;
+
+/// Nesting level of derived types at which collections are empty, options are
+/// `None` and enums avoid variants that contain themselves, so that recursive
+/// types terminate
+pub const MAX_DEPTH: usize = 6;
+
+thread_local! {
+  static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Whether generation has reached [`MAX_DEPTH`]
+#[doc(hidden)]
+pub fn too_deep() -> bool {
+  DEPTH.get() >= MAX_DEPTH
+}
+
+/// Runs `generate` one nesting level deeper, used by `#[derive(Random)]`
+#[doc(hidden)]
+pub fn nested<T>(generate: impl FnOnce() -> T) -> T {
+  struct Level;
+
+  impl Drop for Level {
+    fn drop(&mut self) {
+      DEPTH.set(DEPTH.get() - 1);
+    }
+  }
+
+  DEPTH.set(DEPTH.get() + 1);
+  let _level = Level;
+  generate()
+}
+
+/// Types that can produce a random instance of themselves
+pub trait Random: Sized {
+  /// Generates an instance with the given generator
+  fn random_with(rng: &mut Rng) -> Self;
+
+  /// Generates an instance with a freshly seeded generator
+  fn random() -> Self {
+    Self::random_with(&mut Rng::from_entropy())
+  }
+}
+
+/// Types that can be generated with a requested length, used by `#[random(len = ..)]`
+pub trait RandomLen: Sized {
+  /// Generates an instance holding `len` elements or characters
+  fn random_len_with(rng: &mut Rng, len: usize) -> Self;
+}
+
+macro_rules! standard {
+  ($($ty:ty),*) => {
+    $(
+      impl Random for $ty {
+        fn random_with(rng: &mut Rng) -> Self {
+          rand::Rng::random(rng)
+        }
+      }
+    )*
+  };
+}
+
+// Floats are in `[0, 1)` and chars are any Unicode scalar value.
+standard!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, char);
+
+impl Random for usize {
+  fn random_with(rng: &mut Rng) -> Self {
+    u64::random_with(rng) as usize
+  }
+}
+
+impl Random for isize {
+  fn random_with(rng: &mut Rng) -> Self {
+    i64::random_with(rng) as isize
+  }
+}
+
+impl Random for bool {
+  fn random_with(rng: &mut Rng) -> Self {
+    rng.random_bool()
+  }
+}
+
+impl Random for () {
+  fn random_with(_: &mut Rng) -> Self {}
+}
+
+/// Alphanumeric ASCII of up to [`MAX_LEN`] characters
+impl Random for String {
+  fn random_with(rng: &mut Rng) -> Self {
+    let len = rng.min_max(0, MAX_LEN as i32) as usize;
+    Self::random_len_with(rng, len)
+  }
+}
+
+impl RandomLen for String {
+  fn random_len_with(rng: &mut Rng, len: usize) -> Self {
+    (0..len)
+      .map(|_| char::from(rand::Rng::sample(rng, rand::distr::Alphanumeric)))
+      .collect()
+  }
+}
+
+/// Up to [`MAX_LEN`] elements, none at [`MAX_DEPTH`]
+impl<T: Random> Random for Vec<T> {
+  fn random_with(rng: &mut Rng) -> Self {
+    let len = rng.min_max(0, MAX_LEN as i32) as usize;
+    Self::random_len_with(rng, len)
+  }
+}
+
+impl<T: Random> RandomLen for Vec<T> {
+  fn random_len_with(rng: &mut Rng, len: usize) -> Self {
+    let len = if too_deep() { 0 } else { len };
+    (0..len).map(|_| T::random_with(rng)).collect()
+  }
+}
+
+/// Up to [`MAX_LEN`] entries, fewer if keys collide, none at [`MAX_DEPTH`]
+impl<K: Random + Eq + Hash, V: Random> Random for HashMap<K, V> {
+  fn random_with(rng: &mut Rng) -> Self {
+    let len = rng.min_max(0, MAX_LEN as i32) as usize;
+    Self::random_len_with(rng, len)
+  }
+}
+
+impl<K: Random + Eq + Hash, V: Random> RandomLen for HashMap<K, V> {
+  fn random_len_with(rng: &mut Rng, len: usize) -> Self {
+    let len = if too_deep() { 0 } else { len };
+    (0..len)
+      .map(|_| (K::random_with(rng), V::random_with(rng)))
+      .collect()
+  }
+}
+
+/// `None` half of the time, always at [`MAX_DEPTH`]
+impl<T: Random> Random for Option<T> {
+  fn random_with(rng: &mut Rng) -> Self {
+    (!too_deep() && rng.random_bool()).then(|| T::random_with(rng))
+  }
+}
+
+impl<T: Random> Random for Box<T> {
+  fn random_with(rng: &mut Rng) -> Self {
+    Box::new(T::random_with(rng))
+  }
+}
+
+impl<T: Random, const N: usize> Random for [T; N] {
+  fn random_with(rng: &mut Rng) -> Self {
+    std::array::from_fn(|_| T::random_with(rng))
+  }
+}
+
+macro_rules! tuple {
+  ($($name:ident),+) => {
+    impl<$($name: Random),+> Random for ($($name,)+) {
+      fn random_with(rng: &mut Rng) -> Self {
+        ($($name::random_with(rng),)+)
+      }
+    }
+  };
+}
+
+tuple!(A);
+tuple!(A, B);
+tuple!(A, B, C);
+tuple!(A, B, C, D);
+tuple!(A, B, C, D, E);
+tuple!(A, B, C, D, E, F);
+
+#[cfg(test)]
+mod tests {
+  use crate::rand::{Random, RandomLen, Rng};
+
+  #[test]
+  fn test_builtin() {
+    let mut rng = Rng::seeded(3);
+    for _ in 0..100 {
+      let text = String::random_with(&mut rng);
+      assert!(text.len() <= 8 && text.chars().all(|c| c.is_ascii_alphanumeric()));
+      assert!((0.0..1.0).contains(&f64::random_with(&mut rng)));
+      assert!(Vec::<u8>::random_with(&mut rng).len() <= 8);
+    }
+    assert_eq!(Vec::<u8>::random_len_with(&mut rng, 20).len(), 20);
+    let (a, b) = (
+      <(u8, [i64; 3], Option<bool>)>::random_with(&mut Rng::seeded(4)),
+      <(u8, [i64; 3], Option<bool>)>::random_with(&mut Rng::seeded(4)),
+    );
+    assert_eq!(a, b);
+  }
+
+  #[cfg(feature = "derive")]
+  mod derive {
+    use std::collections::HashMap;
+
+    use crate::rand::random::MAX_DEPTH;
+    use crate::rand::{Random, Rng};
+
+    #[derive(Debug, Clone, PartialEq, Random)]
+    enum Shape {
+      #[random(weight = 3)]
+      Circle(#[random(range = 1.0..10.0)] f64),
+      Square {
+        #[random(range = 1..=4)]
+        side: u32,
+      },
+      #[random(skip)]
+      #[allow(dead_code)]
+      Never,
+      Dot,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Random)]
+    struct Fixture<T> {
+      #[random(range = -5..5)]
+      offset: i64,
+      #[random(len = 3)]
+      code: String,
+      #[random(len = 1..=2)]
+      values: Vec<T>,
+      #[random(choose = ["red", "green"])]
+      color: String,
+      #[random(with = even)]
+      even: u32,
+      #[random(default)]
+      note: Option<String>,
+      shapes: [Shape; 4],
+      pair: (bool, Option<u8>),
+      lookup: HashMap<u8, char>,
+      nested: Option<Box<Unit>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Random)]
+    struct Unit;
+
+    fn even(rng: &mut Rng) -> u32 {
+      rng.min_max(0, 50) as u32 * 2
+    }
+
+    #[test]
+    fn test_derive() {
+      let mut rng = Rng::seeded(1);
+      let mut circles = 0;
+      for _ in 0..500 {
+        let fixture = Fixture::<u16>::random_with(&mut rng);
+        assert!((-5..5).contains(&fixture.offset));
+        assert_eq!(fixture.code.len(), 3);
+        assert!((1..=2).contains(&fixture.values.len()));
+        assert!(["red", "green"].contains(&fixture.color.as_str()));
+        assert_eq!(fixture.even % 2, 0);
+        assert_eq!(fixture.note, None);
+        for shape in &fixture.shapes {
+          match shape {
+            Shape::Circle(r) => {
+              assert!((1.0..10.0).contains(r));
+              circles += 1;
+            }
+            Shape::Square { side } => assert!((1..=4).contains(side)),
+            Shape::Never => panic!("skipped variant generated"),
+            Shape::Dot => {}
+          }
+        }
+      }
+      // 3 in 5 shapes are circles
+      assert!((1_050..1_350).contains(&circles), "{circles}");
+
+      let a = Fixture::<String>::random_with(&mut Rng::seeded(2));
+      let b = Fixture::<String>::random_with(&mut Rng::seeded(2));
+      assert_eq!(a, b);
+      let _ = Fixture::<Unit>::random();
+    }
+
+    #[derive(Debug, Random)]
+    enum Json {
+      Null,
+      Arr(Vec<Json>),
+      Pair(Box<Json>, Box<Json>),
+    }
+
+    impl Json {
+      fn depth(&self) -> usize {
+        match self {
+          Json::Null => 1,
+          Json::Arr(items) => 1 + items.iter().map(Json::depth).max().unwrap_or(0),
+          Json::Pair(a, b) => 1 + a.depth().max(b.depth()),
+        }
+      }
+    }
+
+    #[derive(Debug, Random)]
+    struct Node {
+      children: Vec<Node>,
+      #[random(len = 1..=2)]
+      required: Vec<Node>,
+      parent: Option<Box<Node>>,
+    }
+
+    impl Node {
+      fn depth(&self) -> usize {
+        let children = self.children.iter().chain(&self.required);
+        1 + children.map(Node::depth).max().unwrap_or(0)
+      }
+    }
+
+    #[test]
+    fn test_derive_recursive() {
+      let mut rng = Rng::seeded(0);
+      for _ in 0..20 {
+        assert!(Json::random_with(&mut rng).depth() <= MAX_DEPTH);
+        let node = Node::random_with(&mut rng);
+        assert!(node.depth() <= MAX_DEPTH);
+        let mut node = Some(node);
+        let mut parents = 0;
+        while let Some(next) = node {
+          node = next.parent.map(|parent| *parent);
+          parents += 1;
+        }
+        assert!(parents <= MAX_DEPTH);
+      }
+    }
+  }
+}
@@ -0,0 +1,684 @@
+//! Weighted selection and sampling without replacement
+//!
+//! Every function uses the thread-local generator. The same operations are
+//! available as methods of [`Rng`](super::Rng) for reproducible results.
+
+use std::collections::HashMap;
+
+use rand::seq::index;
+use rand::{Rng as _, RngCore};
+
+pub use rand::distr::weighted::Error as WeightError;
+
+/// Alias table for repeated weighted draws in constant time (Vose's alias method)
+///
+/// Building the table is `O(n)`; every draw afterwards is `O(1)`, which pays off
+/// as soon as more than a handful of draws are made from the same weights.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::sample::WeightedAlias;
+///
+/// let buckets = ["control", "variant-a", "variant-b"];
+/// let alias = WeightedAlias::new(&[0.8, 0.1, 0.1]).unwrap();
+/// let bucket = buckets[alias.sample()];
+/// println!("assigned to {bucket}");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedAlias {
+  prob: Vec<f64>,
+  alias: Vec<usize>,
+}
+
+impl WeightedAlias {
+  /// Builds the alias table for the given weights
+  ///
+  /// # Errors
+  ///
+  /// - `WeightError::InvalidInput` if `weights` is empty
+  /// - `WeightError::InvalidWeight` if a weight is negative or not finite
+  /// - `WeightError::InsufficientNonZero` if all weights are zero
+  pub fn new(weights: &[f64]) -> Result<Self, WeightError> {
+    let total = check_weights(weights)?;
+    let n = weights.len();
+    let mut scaled: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+    let mut prob = vec![1.0; n];
+    let mut alias: Vec<usize> = (0..n).collect();
+    let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..n).partition(|&i| scaled[i] < 1.0);
+    while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+      small.pop();
+      prob[s] = scaled[s];
+      alias[s] = l;
+      scaled[l] -= 1.0 - scaled[s];
+      if scaled[l] < 1.0 {
+        large.pop();
+        small.push(l);
+      }
+    }
+    // Leftovers are 1.0 up to rounding errors.
+    for i in small.into_iter().chain(large) {
+      prob[i] = 1.0;
+    }
+    Ok(Self { prob, alias })
+  }
+
+  /// Draws an index with the thread-local generator
+  pub fn sample(&self) -> usize {
+    self.sample_with(&mut rand::rng())
+  }
+
+  /// Draws an index with the given generator
+  pub fn sample_with<R: RngCore + ?Sized>(&self, rng: &mut R) -> usize {
+    let column = rng.random_range(0..self.prob.len());
+    if rng.random::<f64>() < self.prob[column] {
+      column
+    } else {
+      self.alias[column]
+    }
+  }
+
+  /// Number of weights in the table
+  pub fn len(&self) -> usize {
+    self.prob.len()
+  }
+
+  /// Always `false`, a table cannot be built from no weights
+  pub fn is_empty(&self) -> bool {
+    self.prob.is_empty()
+  }
+}
+
+/// Validates weights, returning their sum
+pub(crate) fn check_weights(weights: &[f64]) -> Result<f64, WeightError> {
+  if weights.is_empty() {
+    return Err(WeightError::InvalidInput);
+  }
+  if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+    return Err(WeightError::InvalidWeight);
+  }
+  let total: f64 = weights.iter().sum();
+  if total <= 0.0 {
+    return Err(WeightError::InsufficientNonZero);
+  }
+  if !total.is_finite() {
+    return Err(WeightError::Overflow);
+  }
+  Ok(total)
+}
+
+/// Randomly selects an element of `items`, with probability proportional to its weight
+///
+/// This is `O(n)` per draw; build a [`WeightedAlias`] for repeated draws.
+///
+/// # Parameters
+///
+/// - `items`: The elements to choose from
+/// - `weights`: One non-negative weight per element
+///
+/// # Returns
+///
+/// A reference to the chosen element, or a `WeightError` if the lengths differ
+/// or the weights are invalid
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::sample::weighted_choice;
+///
+/// let prizes = ["common", "rare", "legendary"];
+/// let prize = weighted_choice(&prizes, &[90.0, 9.0, 1.0]).unwrap();
+/// println!("You won a {prize} item");
+/// ```
+pub fn weighted_choice<'a, T>(items: &'a [T], weights: &[f64]) -> Result<&'a T, WeightError> {
+  weighted_choice_with(&mut rand::rng(), items, weights)
+}
+
+pub(crate) fn weighted_choice_with<'a, T, R: RngCore + ?Sized>(
+  rng: &mut R,
+  items: &'a [T],
+  weights: &[f64],
+) -> Result<&'a T, WeightError> {
+  if items.len() != weights.len() {
+    return Err(WeightError::InvalidInput);
+  }
+  let total = check_weights(weights)?;
+  let mut target = rng.random::<f64>() * total;
+  for (item, weight) in items.iter().zip(weights) {
+    if target < *weight {
+      return Ok(item);
+    }
+    target -= weight;
+  }
+  // Rounding may leave a tiny remainder, fall back to the last non-zero weight.
+  let last = weights
+    .iter()
+    .rposition(|w| *w > 0.0)
+    .expect("checked above");
+  Ok(&items[last])
+}
+
+/// Randomly selects `k` distinct elements of a slice
+///
+/// # Returns
+///
+/// `k` references in random order, or every element shuffled if `k` exceeds
+/// the length of the slice
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::sample::sample;
+///
+/// let players = ["ann", "bob", "cid", "dee", "eve"];
+/// let team = sample(&players, 3);
+/// assert_eq!(team.len(), 3);
+/// ```
+pub fn sample<T>(slice: &[T], k: usize) -> Vec<&T> {
+  sample_with(&mut rand::rng(), slice, k)
+}
+
+pub(crate) fn sample_with<'a, T, R: RngCore + ?Sized>(
+  rng: &mut R,
+  slice: &'a [T],
+  k: usize,
+) -> Vec<&'a T> {
+  index::sample(rng, slice.len(), k.min(slice.len()))
+    .into_iter()
+    .map(|i| &slice[i])
+    .collect()
+}
+
+/// Selects `k` elements uniformly from an iterator of unknown length
+///
+/// Consumes the iterator once and keeps only `k` elements in memory, using
+/// Li's "Algorithm L" which skips over most elements without drawing for them.
+///
+/// # Returns
+///
+/// Up to `k` elements; fewer if the iterator is shorter. The order of the
+/// returned elements is not meaningful.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::sample::reservoir;
+///
+/// let log_lines = (0..1_000_000).map(|i| format!("line {i}"));
+/// let kept = reservoir(log_lines, 100);
+/// assert_eq!(kept.len(), 100);
+/// ```
+pub fn reservoir<I: IntoIterator>(iter: I, k: usize) -> Vec<I::Item> {
+  reservoir_with(&mut rand::rng(), iter, k)
+}
+
+pub(crate) fn reservoir_with<I: IntoIterator, R: RngCore + ?Sized>(
+  rng: &mut R,
+  iter: I,
+  k: usize,
+) -> Vec<I::Item> {
+  let mut iter = iter.into_iter();
+  let mut kept: Vec<I::Item> = iter.by_ref().take(k).collect();
+  if kept.len() < k || k == 0 {
+    return kept;
+  }
+  // `1 - random()` lies in (0, 1], which keeps the logarithms finite.
+  let mut unit = || 1.0 - rng.random::<f64>();
+  let mut w = (unit().ln() / k as f64).exp();
+  loop {
+    let skip = (unit().ln() / (1.0 - w).ln()).floor();
+    let skip = if skip.is_finite() && skip < usize::MAX as f64 {
+      skip as usize
+    } else {
+      usize::MAX
+    };
+    match iter.nth(skip) {
+      Some(item) => {
+        let slot = (unit() * k as f64) as usize;
+        kept[slot.min(k - 1)] = item;
+        w *= (unit().ln() / k as f64).exp();
+      }
+      None => return kept,
+    }
+  }
+}
+
+/// Selects one element uniformly from an iterator, in a single pass
+///
+/// Unlike [`random_choice`](super::random_choice), the elements never need to
+/// be collected into a slice; only a few random numbers are drawn however long
+/// the iterator is.
+///
+/// # Returns
+///
+/// The chosen element, or `None` if the iterator is empty
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::sample::choose_iter;
+///
+/// let line = choose_iter("first\nsecond\nthird".lines()).unwrap();
+/// assert!(["first", "second", "third"].contains(&line));
+/// assert_eq!(choose_iter(std::iter::empty::<u8>()), None);
+/// ```
+pub fn choose_iter<I: IntoIterator>(iter: I) -> Option<I::Item> {
+  choose_iter_with(&mut rand::rng(), iter)
+}
+
+pub(crate) fn choose_iter_with<I: IntoIterator, R: RngCore + ?Sized>(
+  rng: &mut R,
+  iter: I,
+) -> Option<I::Item> {
+  reservoir_with(rng, iter, 1).pop()
+}
+
+/// Randomly selects a key of `map`, with probability proportional to its value
+///
+/// Walks the map once without collecting keys or weights (Chao's weighted
+/// reservoir with a single slot).
+///
+/// # Returns
+///
+/// A reference to the chosen key, or a `WeightError` if the map is empty or
+/// the weights are invalid
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use helpers::rand::sample::choose_weighted_map;
+///
+/// let servers = HashMap::from([("eu-1", 3.0), ("eu-2", 1.0), ("us-1", 0.0)]);
+/// let server = choose_weighted_map(&servers).unwrap();
+/// assert_ne!(*server, "us-1");
+/// ```
+pub fn choose_weighted_map<K, S>(map: &HashMap<K, f64, S>) -> Result<&K, WeightError> {
+  choose_weighted_map_with(&mut rand::rng(), map)
+}
+
+pub(crate) fn choose_weighted_map_with<'a, K, S, R: RngCore + ?Sized>(
+  rng: &mut R,
+  map: &'a HashMap<K, f64, S>,
+) -> Result<&'a K, WeightError> {
+  if map.is_empty() {
+    return Err(WeightError::InvalidInput);
+  }
+  let mut total = 0.0;
+  let mut chosen = None;
+  for (key, &weight) in map {
+    if !weight.is_finite() || weight < 0.0 {
+      return Err(WeightError::InvalidWeight);
+    }
+    total += weight;
+    // Replacing with probability `weight / total` keeps every key seen so far
+    // selected in proportion to its weight.
+    if weight > 0.0 && rng.random::<f64>() * total < weight {
+      chosen = Some(key);
+    }
+  }
+  if !total.is_finite() {
+    return Err(WeightError::Overflow);
+  }
+  chosen.ok_or(WeightError::InsufficientNonZero)
+}
+
+/// Weighted index whose weights can be changed in `O(log n)` (Fenwick tree)
+///
+/// Draws are `O(log n)` as well. Where [`WeightedAlias`] suits fixed weights,
+/// this suits weights that keep moving, such as backends weighted by their
+/// measured health. Weights may all be zero for a while, in which case
+/// nothing can be drawn.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::sample::WeightedIndex;
+///
+/// let backends = ["a", "b", "c"];
+/// let mut index = WeightedIndex::new(&[1.0, 1.0, 1.0]).unwrap();
+/// // "b" starts failing, send it less traffic
+/// index.update(1, 0.1).unwrap();
+/// let backend = backends[index.sample().unwrap()];
+/// println!("routing to {backend}");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedIndex {
+  weights: Vec<f64>,
+  /// `tree[k - 1]` holds the sum of the weights in `k - lowbit(k)..k`
+  tree: Vec<f64>,
+  /// Updates since the tree was last rebuilt from `weights`
+  updates: usize,
+}
+
+impl WeightedIndex {
+  /// Builds the index in `O(n)`
+  ///
+  /// # Errors
+  ///
+  /// - `WeightError::InvalidWeight` if a weight is negative or not finite
+  /// - `WeightError::Overflow` if the weights add up to infinity
+  pub fn new(weights: &[f64]) -> Result<Self, WeightError> {
+    if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+      return Err(WeightError::InvalidWeight);
+    }
+    if !weights.iter().sum::<f64>().is_finite() {
+      return Err(WeightError::Overflow);
+    }
+    let mut index = Self {
+      weights: weights.to_vec(),
+      tree: Vec::new(),
+      updates: 0,
+    };
+    index.rebuild();
+    Ok(index)
+  }
+
+  /// Number of weights
+  pub fn len(&self) -> usize {
+    self.weights.len()
+  }
+
+  /// Whether the index holds no weights
+  pub fn is_empty(&self) -> bool {
+    self.weights.is_empty()
+  }
+
+  /// Weight of index `i`, or `None` if out of bounds
+  pub fn weight(&self, i: usize) -> Option<f64> {
+    self.weights.get(i).copied()
+  }
+
+  /// Sum of all weights
+  pub fn total(&self) -> f64 {
+    self.prefix(self.len())
+  }
+
+  /// Replaces the weight of index `i`
+  ///
+  /// # Errors
+  ///
+  /// - `WeightError::InvalidWeight` if `weight` is negative or not finite
+  /// - `WeightError::Overflow` if the weights would add up to infinity
+  ///
+  /// # Panics
+  ///
+  /// If `i` is out of bounds
+  pub fn update(&mut self, i: usize, weight: f64) -> Result<(), WeightError> {
+    if !weight.is_finite() || weight < 0.0 {
+      return Err(WeightError::InvalidWeight);
+    }
+    let delta = weight - self.weights[i];
+    if !(self.total() + delta).is_finite() {
+      return Err(WeightError::Overflow);
+    }
+    self.weights[i] = weight;
+    // Adding deltas slowly accumulates rounding errors, so the tree is
+    // rebuilt from the exact weights every `n` updates: still `O(1)` amortized.
+    self.updates += 1;
+    if self.updates >= self.len().max(64) {
+      self.rebuild();
+      return Ok(());
+    }
+    let mut k = i + 1;
+    while k <= self.len() {
+      self.tree[k - 1] += delta;
+      k += lowbit(k);
+    }
+    Ok(())
+  }
+
+  /// Appends a weight in `O(log n)`
+  ///
+  /// # Errors
+  ///
+  /// Same as [`update`](Self::update)
+  pub fn push(&mut self, weight: f64) -> Result<(), WeightError> {
+    if !weight.is_finite() || weight < 0.0 {
+      return Err(WeightError::InvalidWeight);
+    }
+    let n = self.len();
+    if !(self.total() + weight).is_finite() {
+      return Err(WeightError::Overflow);
+    }
+    let k = n + 1;
+    let node = weight + self.prefix(n) - self.prefix(k - lowbit(k));
+    self.weights.push(weight);
+    self.tree.push(node);
+    Ok(())
+  }
+
+  /// Draws an index with the thread-local generator
+  ///
+  /// # Returns
+  ///
+  /// The chosen index, or `None` if the index is empty or all weights are zero
+  pub fn sample(&self) -> Option<usize> {
+    self.sample_with(&mut rand::rng())
+  }
+
+  /// Draws an index with the given generator
+  pub fn sample_with<R: RngCore + ?Sized>(&self, rng: &mut R) -> Option<usize> {
+    let total = self.total();
+    if total <= 0.0 {
+      return None;
+    }
+    // Descends to the first index whose prefix sum exceeds the target.
+    let mut target = rng.random::<f64>() * total;
+    let mut pos = 0;
+    let mut step = self.len().checked_ilog2().map_or(0, |bits| 1 << bits);
+    while step > 0 {
+      if pos + step <= self.len() && self.tree[pos + step - 1] <= target {
+        pos += step;
+        target -= self.tree[pos - 1];
+      }
+      step >>= 1;
+    }
+    if self.weights.get(pos).is_some_and(|w| *w > 0.0) {
+      return Some(pos);
+    }
+    // Rounding may overshoot, fall back to the last non-zero weight.
+    self.weights.iter().rposition(|w| *w > 0.0)
+  }
+
+  /// Sum of the first `k` weights
+  fn prefix(&self, mut k: usize) -> f64 {
+    let mut sum = 0.0;
+    while k > 0 {
+      sum += self.tree[k - 1];
+      k -= lowbit(k);
+    }
+    sum
+  }
+
+  fn rebuild(&mut self) {
+    let n = self.len();
+    self.tree.clone_from(&self.weights);
+    for k in 1..=n {
+      let parent = k + lowbit(k);
+      if parent <= n {
+        self.tree[parent - 1] += self.tree[k - 1];
+      }
+    }
+    self.updates = 0;
+  }
+}
+
+/// Lowest set bit of `k`
+fn lowbit(k: usize) -> usize {
+  k & k.wrapping_neg()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use crate::rand::sample::{
+    choose_iter, choose_weighted_map, reservoir, sample, weighted_choice, WeightError,
+    WeightedAlias, WeightedIndex,
+  };
+  use crate::rand::Rng;
+
+  #[test]
+  fn test_weighted_alias() {
+    let weights = [1.0, 0.0, 3.0, 6.0];
+    let alias = WeightedAlias::new(&weights).unwrap();
+    let mut rng = Rng::seeded(1);
+    let mut counts = [0usize; 4];
+    let draws = 100_000;
+    for _ in 0..draws {
+      counts[alias.sample_with(&mut rng)] += 1;
+    }
+    assert_eq!(counts[1], 0);
+    for (count, weight) in counts.iter().zip(weights) {
+      let expected = weight / 10.0;
+      assert!((*count as f64 / draws as f64 - expected).abs() < 0.01);
+    }
+    assert_eq!(WeightedAlias::new(&[]), Err(WeightError::InvalidInput));
+    assert_eq!(
+      WeightedAlias::new(&[1.0, -1.0]),
+      Err(WeightError::InvalidWeight)
+    );
+    assert_eq!(
+      WeightedAlias::new(&[0.0, 0.0]),
+      Err(WeightError::InsufficientNonZero)
+    );
+  }
+
+  #[test]
+  fn test_weighted_choice() {
+    let items = ["a", "b", "c"];
+    for _ in 0..100 {
+      assert_ne!(weighted_choice(&items, &[1.0, 0.0, 1.0]), Ok(&"b"));
+    }
+    assert_eq!(
+      weighted_choice(&items, &[1.0]),
+      Err(WeightError::InvalidInput)
+    );
+    let mut rng = Rng::seeded(2);
+    let hits = (0..10_000)
+      .filter(|_| rng.weighted_choice(&items, &[1.0, 1.0, 8.0]) == Ok(&"c"))
+      .count();
+    assert!((7_700..8_300).contains(&hits));
+  }
+
+  #[test]
+  fn test_sample() {
+    let items: Vec<usize> = (0..20).collect();
+    let picked = sample(&items, 5);
+    assert_eq!(picked.len(), 5);
+    let mut unique = picked.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 5);
+    assert_eq!(sample(&items, 50).len(), 20);
+    assert!(sample::<u8>(&[], 3).is_empty());
+
+    let (mut a, mut b) = (Rng::seeded(4), Rng::seeded(4));
+    assert_eq!(a.sample(&items, 4), b.sample(&items, 4));
+  }
+
+  #[test]
+  fn test_reservoir() {
+    assert_eq!(reservoir(0..3, 5).len(), 3);
+    assert!(reservoir(0..3, 0).is_empty());
+
+    let mut rng = Rng::seeded(5);
+    let mut counts = [0usize; 10];
+    let trials = 20_000;
+    for _ in 0..trials {
+      for item in rng.reservoir(0..10, 3) {
+        counts[item] += 1;
+      }
+    }
+    for count in counts {
+      let share = count as f64 / trials as f64;
+      assert!((share - 0.3).abs() < 0.02, "share {share}");
+    }
+  }
+
+  #[test]
+  fn test_choose_iter() {
+    assert_eq!(choose_iter(Vec::<u8>::new()), None);
+    assert_eq!(choose_iter(Some(7)), Some(7));
+    let mut rng = Rng::seeded(6);
+    let mut counts = [0usize; 4];
+    for _ in 0..20_000 {
+      counts[rng.choose_iter((0..4).filter(|_| true)).unwrap()] += 1;
+    }
+    assert!(
+      counts.iter().all(|&c| (4_700..5_300).contains(&c)),
+      "{counts:?}"
+    );
+  }
+
+  #[test]
+  fn test_choose_weighted_map() {
+    let map = HashMap::from([("a", 1.0), ("b", 0.0), ("c", 3.0)]);
+    let mut rng = Rng::seeded(7);
+    let mut hits = 0;
+    for _ in 0..10_000 {
+      let key = rng.choose_weighted_map(&map).unwrap();
+      assert_ne!(*key, "b");
+      hits += usize::from(*key == "c");
+    }
+    assert!((7_200..7_800).contains(&hits), "{hits}");
+    assert_eq!(
+      choose_weighted_map(&HashMap::<u8, f64>::new()),
+      Err(WeightError::InvalidInput)
+    );
+    assert_eq!(
+      choose_weighted_map(&HashMap::from([(1, 0.0)])),
+      Err(WeightError::InsufficientNonZero)
+    );
+    assert_eq!(
+      choose_weighted_map(&HashMap::from([(1, f64::NAN)])),
+      Err(WeightError::InvalidWeight)
+    );
+  }
+
+  #[test]
+  fn test_weighted_index() {
+    let mut index = WeightedIndex::new(&[1.0, 0.0, 3.0, 6.0, 0.0]).unwrap();
+    assert_eq!(index.len(), 5);
+    assert_eq!(index.total(), 10.0);
+    let mut rng = Rng::seeded(8);
+    let mut counts = [0usize; 5];
+    for _ in 0..100_000 {
+      counts[index.sample_with(&mut rng).unwrap()] += 1;
+    }
+    assert_eq!((counts[1], counts[4]), (0, 0));
+    assert!((9_500..10_500).contains(&counts[0]), "{counts:?}");
+    assert!((59_000..61_000).contains(&counts[3]), "{counts:?}");
+
+    index.update(3, 0.0).unwrap();
+    index.update(4, 4.0).unwrap();
+    index.push(2.0).unwrap();
+    assert_eq!(index.weight(5), Some(2.0));
+    assert_eq!(index.total(), 10.0);
+    let mut counts = [0usize; 6];
+    for _ in 0..100_000 {
+      counts[index.sample_with(&mut rng).unwrap()] += 1;
+    }
+    assert_eq!((counts[1], counts[3]), (0, 0));
+    assert!((39_000..41_000).contains(&counts[4]), "{counts:?}");
+    assert!((19_000..21_000).contains(&counts[5]), "{counts:?}");
+
+    // Many updates trigger rebuilds and must keep the sums exact
+    for round in 0..1_000 {
+      index.update(round % 6, (round % 7) as f64).unwrap();
+    }
+    let expected: f64 = (0..6).map(|i| index.weight(i).unwrap()).sum();
+    assert_eq!(index.total(), expected);
+
+    assert_eq!(index.update(0, -1.0), Err(WeightError::InvalidWeight));
+    assert_eq!(index.push(f64::INFINITY), Err(WeightError::InvalidWeight));
+    let mut empty = WeightedIndex::new(&[]).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.sample(), None);
+    empty.push(0.0).unwrap();
+    assert_eq!(empty.sample(), None);
+    empty.push(1.0).unwrap();
+    assert_eq!(empty.sample(), Some(1));
+  }
+}
@@ -0,0 +1,152 @@
+//! Cryptographically secure randomness from the operating system
+//!
+//! Use this module for anything an attacker must not predict: session ids,
+//! API keys, reset tokens, nonces and salts. The functions at the top of
+//! [`rand`](super) and [`Rng`](super::Rng) are tuned for speed and
+//! reproducibility and must not be used for these purposes.
+//!
+//! Every function reads from the OS generator directly and panics if it is
+//! unavailable, which on supported platforms only happens before the kernel
+//! has gathered enough entropy at boot.
+
+use base64::Engine;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore, TryRngCore};
+
+use super::{RangeError, SampleRange, SampleUniform};
+
+/// Returns a handle to the OS random number generator
+///
+/// Useful to pass a secure generator to APIs that take an [`RngCore`].
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::secure;
+/// use rand::seq::SliceRandom;
+///
+/// let mut seats = vec!["a1", "a2", "b1", "b2"];
+/// seats.shuffle(&mut secure::rng());
+/// ```
+pub fn rng() -> impl CryptoRng {
+  OsRng.unwrap_err()
+}
+
+/// Fills `dest` with secure random bytes
+pub fn fill(dest: &mut [u8]) {
+  rng().fill_bytes(dest)
+}
+
+/// Generates `len` secure random bytes
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::secure;
+///
+/// let salt = secure::bytes(16);
+/// assert_eq!(salt.len(), 16);
+/// ```
+pub fn bytes(len: usize) -> Vec<u8> {
+  let mut bytes = vec![0; len];
+  fill(&mut bytes);
+  bytes
+}
+
+/// Generates a secure random `u64`
+pub fn u64() -> u64 {
+  rng().next_u64()
+}
+
+/// Generates a secure random number within a half-open or inclusive range
+///
+/// Same contract as [`rand::range`](super::range), without modulo bias.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::secure;
+///
+/// let otp: u32 = secure::range(0..1_000_000).unwrap();
+/// println!("{otp:06}");
+/// ```
+pub fn range<T, R>(range: R) -> Result<T, RangeError>
+where
+  T: SampleUniform,
+  R: SampleRange<T>,
+{
+  range.sample_single(&mut rng())
+}
+
+/// Generates a token of `len` random bytes encoded as lowercase hex
+///
+/// The token is `2 * len` characters long; 32 bytes give 256 bits of entropy.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::secure;
+///
+/// let session_id = secure::token_hex(32);
+/// assert_eq!(session_id.len(), 64);
+/// ```
+pub fn token_hex(len: usize) -> String {
+  base16ct::lower::encode_string(&bytes(len))
+}
+
+/// Generates a token of `len` random bytes encoded as URL-safe base64 without padding
+///
+/// The token can be placed in URLs, headers and cookies without escaping.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::secure;
+///
+/// let reset_token = secure::token_base64(24);
+/// assert_eq!(reset_token.len(), 32);
+/// ```
+pub fn token_base64(len: usize) -> String {
+  base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes(len))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use crate::rand::secure::{bytes, range, token_base64, token_hex, u64};
+  use crate::rand::RangeError;
+
+  #[test]
+  fn test_bytes() {
+    assert!(bytes(0).is_empty());
+    assert_eq!(bytes(33).len(), 33);
+    assert_ne!(bytes(32), bytes(32));
+    let values: HashSet<u64> = (0..100).map(|_| u64()).collect();
+    assert_eq!(values.len(), 100);
+  }
+
+  #[test]
+  fn test_range() {
+    for _ in 0..1000 {
+      assert!((10..20).contains(&range(10u8..20).unwrap()));
+      assert!((-1.0..=1.0).contains(&range(-1.0..=1.0).unwrap()));
+    }
+    assert_eq!(range(5i32..5), Err(RangeError::EmptyRange));
+  }
+
+  #[test]
+  fn test_tokens() {
+    let hex = token_hex(16);
+    assert_eq!(hex.len(), 32);
+    assert!(hex
+      .chars()
+      .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+    let b64 = token_base64(30);
+    assert_eq!(b64.len(), 40);
+    assert!(b64
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    assert_ne!(token_base64(30), b64);
+  }
+}
@@ -0,0 +1,496 @@
+//! Monte Carlo simulations, random walks and Markov chains
+//!
+//! Trials run in parallel, each with its own generator derived from the seed
+//! of the [`Simulation`] and the index of the trial, so results do not depend
+//! on how trials are scheduled across threads.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use helpers::rand::sim::Simulation;
+//!
+//! // Requests served per hour by 3 servers that are each up 99% of the time
+//! let simulation = Simulation { trials: 10_000, seed: Some(7) };
+//! let summary = simulation
+//!   .summarize(|rng| (0..3).filter(|_| rng.chance(0.99).unwrap()).count() as f64 * 1_000.0)
+//!   .unwrap();
+//! assert!(summary.mean() > 2_900.0);
+//! let (low, high) = summary.confidence_interval(0.95).unwrap();
+//! assert!(low <= summary.mean() && summary.mean() <= high);
+//! println!("p1 capacity: {}", summary.percentile(1.0));
+//! ```
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use rayon::prelude::*;
+
+use super::sample::{WeightError, WeightedAlias};
+use super::{BernoulliError, Rng};
+
+/// How many trials to run and how to seed them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Simulation {
+  /// Number of trials
+  pub trials: usize,
+  /// Seed of the whole simulation, `None` for a different run every time
+  pub seed: Option<u64>,
+}
+
+impl Default for Simulation {
+  /// 10,000 trials, randomly seeded
+  fn default() -> Self {
+    Self {
+      trials: 10_000,
+      seed: None,
+    }
+  }
+}
+
+impl Simulation {
+  /// Runs `trial` once per trial, in parallel
+  ///
+  /// # Returns
+  ///
+  /// The outcomes, ordered by trial index
+  pub fn run<T, F>(&self, trial: F) -> Vec<T>
+  where
+    T: Send,
+    F: Fn(&mut Rng) -> T + Sync,
+  {
+    let seed = self.seed.unwrap_or_else(rand::random);
+    (0..self.trials)
+      .into_par_iter()
+      .map(|i| trial(&mut Self::trial_rng(seed, i)))
+      .collect()
+  }
+
+  /// Runs `trial` once per trial and summarizes the outcomes
+  ///
+  /// # Returns
+  ///
+  /// The [`Summary`], or `None` if there are no trials or an outcome is NaN
+  pub fn summarize<F>(&self, trial: F) -> Option<Summary>
+  where
+    F: Fn(&mut Rng) -> f64 + Sync,
+  {
+    Summary::new(self.run(trial))
+  }
+
+  /// Generator of trial `i`: one ChaCha stream per trial
+  fn trial_rng(seed: u64, i: usize) -> Rng {
+    let mut rng = ChaCha12Rng::seed_from_u64(seed);
+    rng.set_stream(i as u64);
+    Rng(rng)
+  }
+}
+
+/// Summary statistics of a set of outcomes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+  sorted: Vec<f64>,
+  mean: f64,
+  variance: f64,
+}
+
+impl Summary {
+  /// Summarizes `samples`
+  ///
+  /// # Returns
+  ///
+  /// `None` if `samples` is empty or contains NaN
+  pub fn new(mut samples: Vec<f64>) -> Option<Self> {
+    if samples.is_empty() || samples.iter().any(|x| x.is_nan()) {
+      return None;
+    }
+    samples.sort_by(f64::total_cmp);
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = if samples.len() > 1 {
+      samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+    } else {
+      0.0
+    };
+    Some(Self {
+      sorted: samples,
+      mean,
+      variance,
+    })
+  }
+
+  /// Number of outcomes
+  pub fn count(&self) -> usize {
+    self.sorted.len()
+  }
+
+  /// Arithmetic mean
+  pub fn mean(&self) -> f64 {
+    self.mean
+  }
+
+  /// Sample variance, with Bessel's correction; zero for a single outcome
+  pub fn variance(&self) -> f64 {
+    self.variance
+  }
+
+  /// Sample standard deviation
+  pub fn std_dev(&self) -> f64 {
+    self.variance.sqrt()
+  }
+
+  /// Standard error of the mean
+  pub fn std_error(&self) -> f64 {
+    (self.variance / self.count() as f64).sqrt()
+  }
+
+  /// Smallest outcome
+  pub fn min(&self) -> f64 {
+    self.sorted[0]
+  }
+
+  /// Largest outcome
+  pub fn max(&self) -> f64 {
+    self.sorted[self.sorted.len() - 1]
+  }
+
+  /// Outcome below which `p` percent of the outcomes fall
+  ///
+  /// Interpolates linearly between the closest ranks; `p` is clamped to
+  /// `0..=100`.
+  pub fn percentile(&self, p: f64) -> f64 {
+    let rank = p.clamp(0.0, 100.0) / 100.0 * (self.sorted.len() - 1) as f64;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    let (a, b) = (self.sorted[low], self.sorted[high]);
+    if low == high {
+      a
+    } else {
+      a + (b - a) * (rank - low as f64)
+    }
+  }
+
+  /// Outcome in the middle
+  pub fn median(&self) -> f64 {
+    self.percentile(50.0)
+  }
+
+  /// Confidence interval of the mean, using the normal approximation
+  ///
+  /// # Parameters
+  ///
+  /// - `level`: The confidence level, such as `0.95`
+  ///
+  /// # Returns
+  ///
+  /// `(low, high)`, or `None` if `level` is not strictly between 0 and 1
+  pub fn confidence_interval(&self, level: f64) -> Option<(f64, f64)> {
+    if !(level > 0.0 && level < 1.0) {
+      return None;
+    }
+    let margin = normal_quantile(0.5 + level / 2.0) * self.std_error();
+    Some((self.mean - margin, self.mean + margin))
+  }
+}
+
+/// Inverse of the standard normal CDF (Acklam's approximation, relative error below 1.2e-9)
+fn normal_quantile(p: f64) -> f64 {
+  const A: [f64; 6] = [
+    -3.969683028665376e1,
+    2.209460984245205e2,
+    -2.759285104469687e2,
+    1.38357751867269e2,
+    -3.066479806614716e1,
+    2.506628277459239,
+  ];
+  const B: [f64; 5] = [
+    -5.447609879822406e1,
+    1.615858368580409e2,
+    -1.556989798598866e2,
+    6.680131188771972e1,
+    -1.328068155288572e1,
+  ];
+  const C: [f64; 6] = [
+    -7.784894002430293e-3,
+    -3.223964580411365e-1,
+    -2.400758277161838,
+    -2.549732539343734,
+    4.374664141464968,
+    2.938163982698783,
+  ];
+  const D: [f64; 4] = [
+    7.784695709041462e-3,
+    3.224671290700398e-1,
+    2.445134137142996,
+    3.754408661907416,
+  ];
+  const LOW: f64 = 0.02425;
+
+  let tail = |q: f64| {
+    let q = (-2.0 * q.ln()).sqrt();
+    (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+      / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+  };
+  if p < LOW {
+    tail(p)
+  } else if p > 1.0 - LOW {
+    -tail(1.0 - p)
+  } else {
+    let q = p - 0.5;
+    let r = q * q;
+    (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+      / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+  }
+}
+
+/// Walks `steps` steps from `start`, adding whatever `step` returns each time
+///
+/// # Returns
+///
+/// Every position visited, starting with `start`
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::{sim::walk, Rng};
+///
+/// let mut rng = Rng::seeded(1);
+/// // Daily stock price moves
+/// let prices = walk(&mut rng, 100.0, 250, |rng| rng.normal(0.0, 1.5).unwrap());
+/// assert_eq!(prices.len(), 251);
+/// ```
+pub fn walk(
+  rng: &mut Rng,
+  start: f64,
+  steps: usize,
+  mut step: impl FnMut(&mut Rng) -> f64,
+) -> Vec<f64> {
+  let mut position = start;
+  let mut path = Vec::with_capacity(steps + 1);
+  path.push(position);
+  for _ in 0..steps {
+    position += step(rng);
+    path.push(position);
+  }
+  path
+}
+
+/// Walks `steps` steps of one up or down on the integers, going up with probability `up`
+///
+/// # Returns
+///
+/// Every position visited, starting with `start`, or an error if `up` is not
+/// between 0 and 1
+pub fn lattice_walk(
+  rng: &mut Rng,
+  start: i64,
+  steps: usize,
+  up: f64,
+) -> Result<Vec<i64>, BernoulliError> {
+  rng.chance(up)?;
+  let mut position = start;
+  let mut path = Vec::with_capacity(steps + 1);
+  path.push(position);
+  for _ in 0..steps {
+    position += if rng.chance(up)? { 1 } else { -1 };
+    path.push(position);
+  }
+  Ok(path)
+}
+
+/// A Markov chain over the states `0..n`
+///
+/// Each step draws the next state from the row of the current state in
+/// constant time.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::rand::{sim::MarkovChain, Rng};
+///
+/// // 0 = healthy, 1 = degraded, 2 = down
+/// let chain = MarkovChain::new(&[
+///   [0.95, 0.04, 0.01],
+///   [0.30, 0.60, 0.10],
+///   [0.50, 0.00, 0.50],
+/// ])
+/// .unwrap();
+/// let states = chain.walk(&mut Rng::seeded(3), 0, 24);
+/// assert_eq!(states.len(), 25);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkovChain {
+  rows: Vec<WeightedAlias>,
+}
+
+impl MarkovChain {
+  /// Builds a chain from its transition weights
+  ///
+  /// `transitions[i][j]` is the weight of moving from state `i` to state `j`.
+  /// Rows do not need to sum to one, but each needs a non-zero weight: give an
+  /// absorbing state a weight on itself.
+  ///
+  /// # Errors
+  ///
+  /// - `WeightError::InvalidInput` if there are no states or the matrix is not square
+  /// - The errors of [`WeightedAlias::new`] for an invalid row
+  pub fn new<R: AsRef<[f64]>>(transitions: &[R]) -> Result<Self, WeightError> {
+    let n = transitions.len();
+    if n == 0 || transitions.iter().any(|row| row.as_ref().len() != n) {
+      return Err(WeightError::InvalidInput);
+    }
+    let rows = transitions
+      .iter()
+      .map(|row| WeightedAlias::new(row.as_ref()))
+      .collect::<Result<_, _>>()?;
+    Ok(Self { rows })
+  }
+
+  /// Number of states
+  pub fn len(&self) -> usize {
+    self.rows.len()
+  }
+
+  /// Always `false`, a chain has at least one state
+  pub fn is_empty(&self) -> bool {
+    self.rows.is_empty()
+  }
+
+  /// Draws the state following `state`
+  ///
+  /// # Panics
+  ///
+  /// If `state` is not below [`len`](Self::len)
+  pub fn step(&self, rng: &mut Rng, state: usize) -> usize {
+    self.rows[state].sample_with(rng)
+  }
+
+  /// Walks `steps` steps from `start`
+  ///
+  /// # Returns
+  ///
+  /// Every state visited, starting with `start`
+  ///
+  /// # Panics
+  ///
+  /// If `start` is not below [`len`](Self::len)
+  pub fn walk(&self, rng: &mut Rng, start: usize, steps: usize) -> Vec<usize> {
+    assert!(start < self.len(), "state {start} out of bounds");
+    let mut state = start;
+    let mut path = Vec::with_capacity(steps + 1);
+    path.push(state);
+    for _ in 0..steps {
+      state = self.step(rng, state);
+      path.push(state);
+    }
+    path
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::rand::sample::WeightError;
+  use crate::rand::sim::{lattice_walk, normal_quantile, walk, MarkovChain, Simulation, Summary};
+  use crate::rand::Rng;
+
+  #[test]
+  fn test_simulation() {
+    let simulation = Simulation {
+      trials: 1_000,
+      seed: Some(1),
+    };
+    let a = simulation.run(|rng| rng.min_max(0, 1_000_000));
+    assert_eq!(a.len(), 1_000);
+    assert_eq!(a, simulation.run(|rng| rng.min_max(0, 1_000_000)));
+    let other = Simulation {
+      seed: Some(2),
+      ..simulation
+    };
+    assert_ne!(a, other.run(|rng| rng.min_max(0, 1_000_000)));
+    // Trials get distinct generators
+    let mut unique = a.clone();
+    unique.sort();
+    unique.dedup();
+    assert!(unique.len() > 990);
+
+    let summary = Simulation {
+      trials: 20_000,
+      seed: Some(3),
+    }
+    .summarize(|rng| rng.normal(10.0, 2.0).unwrap())
+    .unwrap();
+    assert_eq!(summary.count(), 20_000);
+    assert!((summary.mean() - 10.0).abs() < 0.05);
+    assert!((summary.std_dev() - 2.0).abs() < 0.05);
+    assert!((summary.median() - 10.0).abs() < 0.1);
+    assert!((summary.percentile(97.5) - 13.92).abs() < 0.15);
+    let (low, high) = summary.confidence_interval(0.95).unwrap();
+    assert!(low < 10.0 && 10.0 < high && high - low < 0.1);
+    assert_eq!(summary.confidence_interval(1.0), None);
+
+    let empty = Simulation {
+      trials: 0,
+      seed: None,
+    };
+    assert_eq!(empty.summarize(|_| 1.0), None);
+  }
+
+  #[test]
+  fn test_summary() {
+    let summary = Summary::new(vec![4.0, 1.0, 3.0, 2.0]).unwrap();
+    assert_eq!((summary.min(), summary.max()), (1.0, 4.0));
+    assert_eq!(summary.mean(), 2.5);
+    assert!((summary.variance() - 5.0 / 3.0).abs() < 1e-12);
+    assert_eq!(summary.percentile(0.0), 1.0);
+    assert_eq!(summary.percentile(50.0), 2.5);
+    assert_eq!(summary.percentile(100.0), 4.0);
+    assert_eq!(summary.percentile(150.0), 4.0);
+    assert_eq!(Summary::new(vec![5.0]).unwrap().variance(), 0.0);
+    assert_eq!(Summary::new(vec![]), None);
+    assert_eq!(Summary::new(vec![1.0, f64::NAN]), None);
+  }
+
+  #[test]
+  fn test_normal_quantile() {
+    assert!(normal_quantile(0.5).abs() < 1e-9);
+    assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-6);
+    assert!((normal_quantile(0.005) + 2.575829).abs() < 1e-6);
+  }
+
+  #[test]
+  fn test_walks() {
+    let mut rng = Rng::seeded(4);
+    let path = walk(&mut rng, 1.0, 3, |_| 2.0);
+    assert_eq!(path, [1.0, 3.0, 5.0, 7.0]);
+
+    let path = lattice_walk(&mut rng, 0, 1_000, 0.5).unwrap();
+    assert_eq!(path.len(), 1_001);
+    assert!(path.windows(2).all(|w| (w[1] - w[0]).abs() == 1));
+    assert_eq!(lattice_walk(&mut rng, 5, 10, 1.0).unwrap()[10], 15);
+    assert!(lattice_walk(&mut rng, 0, 10, 1.5).is_err());
+  }
+
+  #[test]
+  fn test_markov_chain() {
+    let chain = MarkovChain::new(&[[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]]).unwrap();
+    assert_eq!(chain.len(), 3);
+    let mut rng = Rng::seeded(5);
+    assert_eq!(chain.walk(&mut rng, 1, 4), [1, 2, 0, 1, 2]);
+
+    // The stationary distribution of this chain is (5/6, 1/6)
+    let chain = MarkovChain::new(&[vec![0.9, 0.1], vec![0.5, 0.5]]).unwrap();
+    let states = chain.walk(&mut rng, 0, 60_000);
+    let share = states.iter().filter(|&&s| s == 1).count() as f64 / states.len() as f64;
+    assert!((share - 1.0 / 6.0).abs() < 0.01, "{share}");
+
+    assert_eq!(
+      MarkovChain::new::<[f64; 0]>(&[]),
+      Err(WeightError::InvalidInput)
+    );
+    assert_eq!(
+      MarkovChain::new(&[vec![1.0, 0.0], vec![1.0]]),
+      Err(WeightError::InvalidInput)
+    );
+    assert_eq!(
+      MarkovChain::new(&[[1.0, 0.0], [0.0, 0.0]]),
+      Err(WeightError::InsufficientNonZero)
+    );
+  }
+}
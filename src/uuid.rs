@@ -1,6 +1,12 @@
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
 pub use uuid::Error;
 use uuid::Uuid;
 
+pub mod snowflake;
+pub mod ulid;
+
 pub struct Alphabet;
 
 impl Alphabet {
@@ -161,9 +167,200 @@ pub fn uuid_v5(name: &[u8]) -> Uuid {
   Uuid::new_v5(&Uuid::NAMESPACE_DNS, name)
 }
 
+/// Standard namespaces for name-based UUIDs (v3 and v5)
+pub struct Namespace;
+
+impl Namespace {
+  /// Fully qualified domain names
+  pub const DNS: Uuid = Uuid::NAMESPACE_DNS;
+
+  /// URLs
+  pub const URL: Uuid = Uuid::NAMESPACE_URL;
+
+  /// ISO object identifiers
+  pub const OID: Uuid = Uuid::NAMESPACE_OID;
+
+  /// X.500 distinguished names
+  pub const X500: Uuid = Uuid::NAMESPACE_X500;
+
+  /// Derives a namespace of your own from a name.
+  ///
+  /// The namespace is the UUID v5 of `name` in the [`URL`](Self::URL)
+  /// namespace, so a URL you control makes a good, collision-free name.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use helpers::uuid::{uuid_v5_in, Namespace};
+  ///
+  /// let orders = Namespace::custom("https://example.com/ns/orders");
+  /// let id = uuid_v5_in(&orders, b"order-1042");
+  /// assert_eq!(id, uuid_v5_in(&Namespace::custom("https://example.com/ns/orders"), b"order-1042"));
+  /// ```
+  pub fn custom(name: &str) -> Uuid {
+    Uuid::new_v5(&Self::URL, name.as_bytes())
+  }
+}
+
+/// Generates a UUID v5 with the given namespace and name.
+///
+/// The same namespace and name always give the same UUID.
+///
+/// # Arguments
+///
+/// * `namespace` - One of the [`Namespace`] constants, or any other UUID.
+/// * `name` - The name to use for generating the UUID v5.
+///
+/// # Returns
+///
+/// A UUID v5.
+///
+/// # Example
+///
+/// ```
+/// use helpers::uuid::{uuid_v5, uuid_v5_in, Namespace};
+///
+/// let uuid = uuid_v5_in(&Namespace::URL, b"https://example.com/");
+/// println!("Generated UUID v5: {}", uuid);
+/// assert_eq!(uuid_v5_in(&Namespace::DNS, b"example.com"), uuid_v5(b"example.com"));
+/// ```
+pub fn uuid_v5_in(namespace: &Uuid, name: &[u8]) -> Uuid {
+  Uuid::new_v5(namespace, name)
+}
+
+/// Generates a UUID v3 with the given namespace and name.
+///
+/// Like v5 but hashed with MD5; prefer v5 unless a system expects v3.
+///
+/// # Arguments
+///
+/// * `namespace` - One of the [`Namespace`] constants, or any other UUID.
+/// * `name` - The name to use for generating the UUID v3.
+///
+/// # Returns
+///
+/// A UUID v3.
+///
+/// # Example
+///
+/// ```
+/// use helpers::uuid::{uuid_v3, Namespace};
+///
+/// let uuid = uuid_v3(&Namespace::DNS, b"example.com");
+/// println!("Generated UUID v3: {}", uuid);
+/// ```
+pub fn uuid_v3(namespace: &Uuid, name: &[u8]) -> Uuid {
+  Uuid::new_v3(namespace, name)
+}
+
+/// Generates a UUID v7.
+///
+/// The first 48 bits hold the Unix time in milliseconds, so UUIDs sort by
+/// creation time and keep database indexes sequential. UUIDs generated within
+/// the same millisecond by this process still increase, thanks to a counter
+/// seeded randomly every millisecond.
+///
+/// # Returns
+///
+/// A UUID v7.
+///
+/// # Example
+///
+/// ```
+/// use helpers::uuid::uuid_v7;
+///
+/// let first = uuid_v7();
+/// let second = uuid_v7();
+/// assert!(first < second);
+/// ```
+pub fn uuid_v7() -> Uuid {
+  Uuid::now_v7()
+}
+
+/// Generates a UUID v6 with a random node ID.
+///
+/// Like v1 with the timestamp fields reordered so UUIDs sort by creation time.
+///
+/// # Returns
+///
+/// A UUID v6.
+///
+/// # Example
+///
+/// ```
+/// use helpers::uuid::uuid_v6;
+///
+/// let uuid = uuid_v6();
+/// println!("Generated UUID v6: {}", uuid);
+/// ```
+pub fn uuid_v6() -> Uuid {
+  Uuid::now_v6(node_id())
+}
+
+/// Generates a UUID v1 with a random node ID.
+///
+/// The node ID is picked once per process instead of using a MAC address,
+/// so UUIDs do not reveal the host that created them.
+///
+/// # Returns
+///
+/// A UUID v1.
+///
+/// # Example
+///
+/// ```
+/// use helpers::uuid::uuid_v1;
+///
+/// let uuid = uuid_v1();
+/// println!("Generated UUID v1: {}", uuid);
+/// ```
+pub fn uuid_v1() -> Uuid {
+  Uuid::now_v1(node_id())
+}
+
+/// Random node ID of this process, with the multicast bit set as RFC 9562
+/// requires for node IDs that are not MAC addresses.
+fn node_id() -> &'static [u8; 6] {
+  static NODE_ID: OnceLock<[u8; 6]> = OnceLock::new();
+  NODE_ID.get_or_init(|| {
+    let mut node: [u8; 6] = rand::random();
+    node[0] |= 0x01;
+    node
+  })
+}
+
+/// Extracts the creation time embedded in a UUID.
+///
+/// # Arguments
+///
+/// * `uuid` - A UUID v1, v6 or v7.
+///
+/// # Returns
+///
+/// The creation time, or `None` for versions without a timestamp.
+///
+/// # Example
+///
+/// ```
+/// use helpers::uuid::{uuid_timestamp, uuid_v4, uuid_v7};
+///
+/// let created = uuid_timestamp(&uuid_v7()).unwrap();
+/// println!("Created at {}", created);
+/// assert_eq!(uuid_timestamp(&uuid_v4()), None);
+/// ```
+pub fn uuid_timestamp(uuid: &Uuid) -> Option<DateTime<Utc>> {
+  let (seconds, nanos) = uuid.get_timestamp()?.to_unix();
+  DateTime::from_timestamp(seconds.try_into().ok()?, nanos)
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::uuid::{nanoid, nanoid_segmented, uuid_v4, uuid_v5, Alphabet};
+  use chrono::{TimeDelta, Utc};
+
+  use crate::uuid::{
+    nanoid, nanoid_segmented, uuid_timestamp, uuid_v1, uuid_v3, uuid_v4, uuid_v5, uuid_v5_in,
+    uuid_v6, uuid_v7, Alphabet, Namespace,
+  };
 
   #[test]
   fn test_nanoid() {
@@ -210,4 +407,63 @@ mod tests {
     let name = b"example.com";
     assert_eq!(uuid_v5(name), uuid_v5(name));
   }
+
+  #[test]
+  fn test_namespaces() {
+    // Reference values from RFC 9562 appendix A and Python's uuid module
+    assert_eq!(
+      uuid_v5_in(&Namespace::DNS, b"www.example.com").to_string(),
+      "2ed6657d-e927-568b-95e1-2665a8aea6a2"
+    );
+    assert_eq!(
+      uuid_v3(&Namespace::DNS, b"www.example.com").to_string(),
+      "5df41881-3aed-3515-88a7-2f4a814cf09e"
+    );
+    assert_eq!(uuid_v5_in(&Namespace::DNS, b"a"), uuid_v5(b"a"));
+    assert_ne!(
+      uuid_v5_in(&Namespace::URL, b"a"),
+      uuid_v5_in(&Namespace::OID, b"a")
+    );
+    assert_eq!(
+      Some(uuid::Version::Md5),
+      uuid_v3(&Namespace::X500, b"cn=a").get_version()
+    );
+
+    let custom = Namespace::custom("https://example.com/ns/users");
+    assert_eq!(
+      custom,
+      uuid_v5_in(&Namespace::URL, b"https://example.com/ns/users")
+    );
+    assert_ne!(uuid_v5_in(&custom, b"42"), uuid_v5(b"42"));
+  }
+
+  #[test]
+  fn test_uuid_v7() {
+    let uuids: Vec<_> = (0..10_000).map(|_| uuid_v7()).collect();
+    assert!(uuids.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(Some(uuid::Version::SortRand), uuids[0].get_version());
+  }
+
+  #[test]
+  fn test_uuid_v1_v6() {
+    let (v1, v6) = (uuid_v1(), uuid_v6());
+    assert_eq!(Some(uuid::Version::Mac), v1.get_version());
+    assert_eq!(Some(uuid::Version::SortMac), v6.get_version());
+    // Both use the same random node ID, with the multicast bit set
+    assert_eq!(v1.as_bytes()[10..], v6.as_bytes()[10..]);
+    assert_eq!(v1.as_bytes()[10] & 0x01, 0x01);
+    let later = uuid_v6();
+    assert!(v6 < later);
+  }
+
+  #[test]
+  fn test_uuid_timestamp() {
+    let now = Utc::now();
+    for uuid in [uuid_v1(), uuid_v6(), uuid_v7()] {
+      let created = uuid_timestamp(&uuid).unwrap();
+      assert!((created - now).abs() < TimeDelta::seconds(5), "{uuid}");
+    }
+    assert_eq!(uuid_timestamp(&uuid_v4()), None);
+    assert_eq!(uuid_timestamp(&uuid_v5(b"example.com")), None);
+  }
 }
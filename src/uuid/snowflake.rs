@@ -0,0 +1,462 @@
+//! Snowflake IDs: 64-bit integers sortable by creation time
+//!
+//! An ID packs, from the most significant bit down, a zero sign bit, the
+//! milliseconds elapsed since a custom epoch, a datacenter id, a worker id
+//! and a per-millisecond sequence. Each process generating IDs needs its own
+//! datacenter and worker pair for IDs to stay unique.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Errors when configuring a generator or generating an ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnowflakeError {
+  /// The bit widths leave no room for the timestamp
+  InvalidLayout,
+  /// The datacenter or worker id does not fit in its bits
+  InvalidNode,
+  /// The clock went back by this many milliseconds, more than allowed
+  ClockMovedBackwards(u64),
+  /// The clock is before the epoch, or so far after it that the timestamp
+  /// no longer fits
+  TimestampOutOfRange,
+}
+
+impl fmt::Display for SnowflakeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SnowflakeError::InvalidLayout => write!(f, "no bits left for the timestamp"),
+      SnowflakeError::InvalidNode => write!(f, "datacenter or worker id too large"),
+      SnowflakeError::ClockMovedBackwards(ms) => write!(f, "clock moved backwards by {ms}ms"),
+      SnowflakeError::TimestampOutOfRange => write!(f, "timestamp out of range"),
+    }
+  }
+}
+
+impl Error for SnowflakeError {}
+
+/// Source of the current time, replaceable in tests
+pub trait Clock: Send + Sync {
+  /// Milliseconds since the Unix epoch
+  fn now_ms(&self) -> u64;
+
+  /// Waits for `duration`
+  fn sleep(&self, duration: Duration) {
+    thread::sleep(duration);
+  }
+}
+
+/// The system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  fn now_ms(&self) -> u64 {
+    Utc::now().timestamp_millis().max(0) as u64
+  }
+}
+
+/// What to do when the clock goes backwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backwards {
+  /// Fail with [`SnowflakeError::ClockMovedBackwards`]
+  Error,
+  /// Sleep until the clock catches up if it went back by at most this
+  /// duration, fail otherwise
+  Wait(Duration),
+}
+
+/// Layout of the IDs
+///
+/// The timestamp gets the bits left over out of 63. The default is Twitter's
+/// layout: 41 bits of timestamp from 2010-11-04, which last about 69 years,
+/// 5 datacenter bits, 5 worker bits and 12 sequence bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::uuid::snowflake::Config;
+///
+/// // One datacenter, more workers, epoch in 2024
+/// let config = Config {
+///   epoch_ms: 1_704_067_200_000,
+///   datacenter_bits: 0,
+///   worker_bits: 10,
+///   ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+  /// Start of the timestamps, in milliseconds since the Unix epoch
+  pub epoch_ms: u64,
+  /// Bits of the datacenter id
+  pub datacenter_bits: u8,
+  /// Bits of the worker id
+  pub worker_bits: u8,
+  /// Bits of the sequence, which bound the IDs per millisecond per worker
+  pub sequence_bits: u8,
+  /// Handling of a clock going backwards
+  pub backwards: Backwards,
+}
+
+impl Default for Config {
+  /// Twitter's layout, waiting up to 10ms for a clock that went backwards
+  fn default() -> Self {
+    Self {
+      epoch_ms: 1_288_834_974_657,
+      datacenter_bits: 5,
+      worker_bits: 5,
+      sequence_bits: 12,
+      backwards: Backwards::Wait(Duration::from_millis(10)),
+    }
+  }
+}
+
+impl Config {
+  /// Bits of the timestamp, or `None` if the layout leaves none
+  fn timestamp_bits(&self) -> Option<u32> {
+    let used =
+      u32::from(self.datacenter_bits) + u32::from(self.worker_bits) + u32::from(self.sequence_bits);
+    63u32.checked_sub(used).filter(|bits| *bits > 0)
+  }
+
+  /// Splits an ID into its parts
+  ///
+  /// # Returns
+  ///
+  /// The parts, or `None` if the ID is negative or the layout is invalid
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use helpers::uuid::snowflake::{Config, Snowflake};
+  ///
+  /// let generator = Snowflake::new(Config::default(), 3, 7).unwrap();
+  /// let id = generator.generate().unwrap();
+  /// let parts = Config::default().decompose(id).unwrap();
+  /// assert_eq!((parts.datacenter, parts.worker), (3, 7));
+  /// println!("created at {}", parts.datetime());
+  /// ```
+  pub fn decompose(&self, id: i64) -> Option<Parts> {
+    self.timestamp_bits()?;
+    let id = u64::try_from(id).ok()?;
+    let field = |shift: u32, bits: u8| (id >> shift) & mask(bits);
+    let worker_shift = u32::from(self.sequence_bits);
+    let datacenter_shift = worker_shift + u32::from(self.worker_bits);
+    let timestamp_shift = datacenter_shift + u32::from(self.datacenter_bits);
+    Some(Parts {
+      timestamp_ms: self.epoch_ms + (id >> timestamp_shift),
+      datacenter: field(datacenter_shift, self.datacenter_bits),
+      worker: field(worker_shift, self.worker_bits),
+      sequence: field(0, self.sequence_bits),
+    })
+  }
+}
+
+fn mask(bits: u8) -> u64 {
+  (1u64 << bits) - 1
+}
+
+/// The parts of an ID, see [`Config::decompose`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parts {
+  /// Creation time in milliseconds since the Unix epoch
+  pub timestamp_ms: u64,
+  /// Datacenter id
+  pub datacenter: u64,
+  /// Worker id
+  pub worker: u64,
+  /// Position among the IDs of the same millisecond
+  pub sequence: u64,
+}
+
+impl Parts {
+  /// Creation time
+  pub fn datetime(&self) -> DateTime<Utc> {
+    i64::try_from(self.timestamp_ms)
+      .ok()
+      .and_then(DateTime::from_timestamp_millis)
+      .unwrap_or(DateTime::<Utc>::MAX_UTC)
+  }
+}
+
+/// A thread-safe Snowflake ID generator
+///
+/// Generates up to `2^sequence_bits` IDs per millisecond, then waits for the
+/// next millisecond.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::uuid::snowflake::{Config, Snowflake};
+///
+/// let generator = Snowflake::new(Config::default(), 1, 12).unwrap();
+/// let first = generator.generate().unwrap();
+/// let second = generator.generate().unwrap();
+/// assert!(0 < first && first < second);
+/// ```
+#[derive(Debug)]
+pub struct Snowflake<C = SystemClock> {
+  config: Config,
+  node: u64,
+  clock: C,
+  /// Timestamp relative to the epoch and sequence of the last ID
+  last: Mutex<(u64, u64)>,
+}
+
+impl Snowflake {
+  /// Creates a generator using the system clock
+  ///
+  /// # Errors
+  ///
+  /// - `SnowflakeError::InvalidLayout` if the layout leaves no bits for the timestamp
+  /// - `SnowflakeError::InvalidNode` if `datacenter` or `worker` does not fit in its bits
+  pub fn new(config: Config, datacenter: u64, worker: u64) -> Result<Self, SnowflakeError> {
+    Self::with_clock(config, datacenter, worker, SystemClock)
+  }
+}
+
+impl<C: Clock> Snowflake<C> {
+  /// Creates a generator reading the time from `clock`
+  pub fn with_clock(
+    config: Config,
+    datacenter: u64,
+    worker: u64,
+    clock: C,
+  ) -> Result<Self, SnowflakeError> {
+    if config.timestamp_bits().is_none() {
+      return Err(SnowflakeError::InvalidLayout);
+    }
+    if datacenter > mask(config.datacenter_bits) || worker > mask(config.worker_bits) {
+      return Err(SnowflakeError::InvalidNode);
+    }
+    let node = (datacenter << config.worker_bits | worker) << config.sequence_bits;
+    Ok(Self {
+      config,
+      node,
+      clock,
+      last: Mutex::new((0, 0)),
+    })
+  }
+
+  /// Layout of the generated IDs
+  pub fn config(&self) -> &Config {
+    &self.config
+  }
+
+  /// Generates the next ID
+  ///
+  /// # Errors
+  ///
+  /// - `SnowflakeError::ClockMovedBackwards` if the clock went back further
+  ///   than [`Config::backwards`] allows
+  /// - `SnowflakeError::TimestampOutOfRange` if the clock is before the epoch
+  ///   or the timestamp bits are exhausted
+  pub fn generate(&self) -> Result<i64, SnowflakeError> {
+    let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+    let (last_ms, last_sequence) = *last;
+    let mut now = self.elapsed()?;
+    if now < last_ms {
+      let behind = last_ms - now;
+      match self.config.backwards {
+        Backwards::Wait(max) if Duration::from_millis(behind) <= max => {
+          while now < last_ms {
+            self.clock.sleep(Duration::from_millis(last_ms - now));
+            now = self.elapsed()?;
+          }
+        }
+        _ => return Err(SnowflakeError::ClockMovedBackwards(behind)),
+      }
+    }
+    let sequence = if now == last_ms {
+      let sequence = (last_sequence + 1) & mask(self.config.sequence_bits);
+      if sequence == 0 {
+        // The sequence is exhausted, wait for the next millisecond.
+        while now <= last_ms {
+          self.clock.sleep(Duration::from_micros(100));
+          now = self.elapsed()?;
+        }
+      }
+      sequence
+    } else {
+      0
+    };
+    *last = (now, sequence);
+    let timestamp_shift =
+      u32::from(self.config.sequence_bits + self.config.worker_bits + self.config.datacenter_bits);
+    Ok((now << timestamp_shift | self.node | sequence) as i64)
+  }
+
+  /// Milliseconds since the epoch, checked against the timestamp bits
+  fn elapsed(&self) -> Result<u64, SnowflakeError> {
+    let bits = self.config.timestamp_bits().expect("checked on creation");
+    self
+      .clock
+      .now_ms()
+      .checked_sub(self.config.epoch_ms)
+      .filter(|elapsed| elapsed >> bits == 0)
+      .ok_or(SnowflakeError::TimestampOutOfRange)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+  use std::sync::atomic::{AtomicU64, Ordering};
+  use std::sync::Arc;
+  use std::thread;
+  use std::time::Duration;
+
+  use crate::uuid::snowflake::{Backwards, Clock, Config, Snowflake, SnowflakeError};
+
+  /// A clock that only moves when told to, or when slept on
+  #[derive(Debug, Default)]
+  struct MockClock(AtomicU64);
+
+  impl MockClock {
+    fn set(&self, ms: u64) {
+      self.0.store(ms, Ordering::SeqCst);
+    }
+  }
+
+  impl Clock for MockClock {
+    fn now_ms(&self) -> u64 {
+      self.0.load(Ordering::SeqCst)
+    }
+
+    fn sleep(&self, duration: Duration) {
+      self
+        .0
+        .fetch_add(duration.as_millis().max(1) as u64, Ordering::SeqCst);
+    }
+  }
+
+  impl Clock for Arc<MockClock> {
+    fn now_ms(&self) -> u64 {
+      self.as_ref().now_ms()
+    }
+
+    fn sleep(&self, duration: Duration) {
+      self.as_ref().sleep(duration)
+    }
+  }
+
+  fn small(backwards: Backwards) -> Config {
+    Config {
+      epoch_ms: 1_000,
+      datacenter_bits: 2,
+      worker_bits: 3,
+      sequence_bits: 2,
+      backwards,
+    }
+  }
+
+  #[test]
+  fn test_layout() {
+    let clock = Arc::new(MockClock::default());
+    clock.set(1_005);
+    let generator = Snowflake::with_clock(small(Backwards::Error), 2, 5, clock.clone()).unwrap();
+    let id = generator.generate().unwrap();
+    // 5ms after the epoch, datacenter 2, worker 5, sequence 0
+    assert_eq!(id, (5 << 7) | (2 << 5) | (5 << 2));
+    let parts = generator.config().decompose(id).unwrap();
+    assert_eq!(
+      (
+        parts.timestamp_ms,
+        parts.datacenter,
+        parts.worker,
+        parts.sequence
+      ),
+      (1_005, 2, 5, 0)
+    );
+    assert_eq!(parts.datetime().timestamp_millis(), 1_005);
+    assert_eq!(Config::default().decompose(-1), None);
+
+    assert!(matches!(
+      Snowflake::new(small(Backwards::Error), 4, 0),
+      Err(SnowflakeError::InvalidNode)
+    ));
+    let full = Config {
+      sequence_bits: 53,
+      ..Default::default()
+    };
+    assert!(matches!(
+      Snowflake::new(full, 0, 0),
+      Err(SnowflakeError::InvalidLayout)
+    ));
+  }
+
+  #[test]
+  fn test_sequence() {
+    let clock = Arc::new(MockClock::default());
+    clock.set(2_000);
+    let generator = Snowflake::with_clock(small(Backwards::Error), 0, 0, clock.clone()).unwrap();
+    let ids: Vec<i64> = (0..10).map(|_| generator.generate().unwrap()).collect();
+    assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    let config = generator.config();
+    let sequences: Vec<u64> = ids
+      .iter()
+      .map(|id| config.decompose(*id).unwrap().sequence)
+      .collect();
+    // Four IDs fit in a millisecond, then the generator waits for the next one
+    assert_eq!(sequences, [0, 1, 2, 3, 0, 1, 2, 3, 0, 1]);
+    assert_eq!(config.decompose(ids[9]).unwrap().timestamp_ms, 2_002);
+  }
+
+  #[test]
+  fn test_backwards() {
+    let clock = Arc::new(MockClock::default());
+    clock.set(5_000);
+    let strict = Snowflake::with_clock(small(Backwards::Error), 0, 0, clock.clone()).unwrap();
+    strict.generate().unwrap();
+    clock.set(4_990);
+    assert_eq!(
+      strict.generate(),
+      Err(SnowflakeError::ClockMovedBackwards(10))
+    );
+
+    clock.set(5_000);
+    let patient = Backwards::Wait(Duration::from_millis(20));
+    let waiting = Snowflake::with_clock(small(patient), 0, 0, clock.clone()).unwrap();
+    let first = waiting.generate().unwrap();
+    clock.set(4_985);
+    let second = waiting.generate().unwrap();
+    assert!(first < second);
+    assert!(clock.now_ms() >= 5_000);
+    clock.set(4_900);
+    assert_eq!(
+      waiting.generate(),
+      Err(SnowflakeError::ClockMovedBackwards(100))
+    );
+
+    clock.set(500);
+    assert_eq!(waiting.generate(), Err(SnowflakeError::TimestampOutOfRange));
+  }
+
+  #[test]
+  fn test_threads() {
+    let generator = Arc::new(Snowflake::new(Config::default(), 1, 1).unwrap());
+    let handles: Vec<_> = (0..4)
+      .map(|_| {
+        let generator = Arc::clone(&generator);
+        thread::spawn(move || {
+          (0..5_000)
+            .map(|_| generator.generate().unwrap())
+            .collect::<Vec<_>>()
+        })
+      })
+      .collect();
+    let mut all = HashSet::new();
+    for handle in handles {
+      let ids = handle.join().unwrap();
+      assert!(ids.windows(2).all(|w| w[0] < w[1]));
+      all.extend(ids);
+    }
+    assert_eq!(all.len(), 20_000);
+  }
+}
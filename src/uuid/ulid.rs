@@ -0,0 +1,341 @@
+//! ULIDs: 128-bit identifiers sortable by creation time
+//!
+//! A ULID holds a 48-bit Unix timestamp in milliseconds followed by 80 random
+//! bits, written as 26 characters of Crockford's base32 such as
+//! `01ARZ3NDEKTSV4RRFFQ69G5FAV`. Both the text and the bytes sort by time.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Crockford's base32 alphabet, without I, L, O and U
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Length of the text form
+pub const ULID_LEN: usize = 26;
+
+const RANDOM_BITS: u32 = 80;
+const RANDOM_MASK: u128 = (1 << RANDOM_BITS) - 1;
+const TIMESTAMP_MASK: u64 = (1 << 48) - 1;
+
+/// Errors when parsing or generating a ULID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UlidError {
+  /// The text is not 26 characters long
+  InvalidLength,
+  /// The text contains a character outside of Crockford's base32
+  InvalidChar,
+  /// The text encodes more than 128 bits, or the random part of a
+  /// [`Generator`] ran out within one millisecond
+  Overflow,
+}
+
+impl fmt::Display for UlidError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      UlidError::InvalidLength => write!(f, "a ULID must be {ULID_LEN} characters long"),
+      UlidError::InvalidChar => write!(f, "invalid character in ULID"),
+      UlidError::Overflow => write!(f, "ULID overflow"),
+    }
+  }
+}
+
+impl Error for UlidError {}
+
+/// A ULID
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::uuid::ulid::Ulid;
+///
+/// let id = Ulid::new();
+/// let text = id.to_string();
+/// assert_eq!(text.len(), 26);
+/// assert_eq!(text.parse::<Ulid>(), Ok(id));
+/// println!("created at {}", id.datetime());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ulid(u128);
+
+impl Ulid {
+  /// Generates a ULID with the current time and fresh random bits
+  ///
+  /// ULIDs created within the same millisecond are in random order; use a
+  /// [`Generator`] when that matters.
+  pub fn new() -> Self {
+    Self::from_parts(now_ms(), rand::random())
+  }
+
+  /// Builds a ULID from a timestamp in milliseconds and random bits
+  ///
+  /// Only the low 48 bits of `timestamp_ms` and the low 80 bits of `random`
+  /// are used.
+  pub fn from_parts(timestamp_ms: u64, random: u128) -> Self {
+    Self(u128::from(timestamp_ms & TIMESTAMP_MASK) << RANDOM_BITS | random & RANDOM_MASK)
+  }
+
+  /// Milliseconds since the Unix epoch
+  pub fn timestamp_ms(&self) -> u64 {
+    (self.0 >> RANDOM_BITS) as u64
+  }
+
+  /// The random part, in the low 80 bits
+  pub fn random(&self) -> u128 {
+    self.0 & RANDOM_MASK
+  }
+
+  /// Creation time
+  pub fn datetime(&self) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(self.timestamp_ms() as i64)
+      .expect("48-bit timestamps are within chrono's range")
+  }
+
+  /// The 128 bits as an integer
+  pub fn to_u128(&self) -> u128 {
+    self.0
+  }
+
+  /// Builds a ULID from its 128 bits
+  pub fn from_u128(value: u128) -> Self {
+    Self(value)
+  }
+
+  /// Big-endian bytes, which sort like the ULID
+  pub fn to_bytes(&self) -> [u8; 16] {
+    self.0.to_be_bytes()
+  }
+
+  /// Builds a ULID from its big-endian bytes
+  pub fn from_bytes(bytes: [u8; 16]) -> Self {
+    Self(u128::from_be_bytes(bytes))
+  }
+
+  /// Converts to a `Uuid` with the same bits
+  ///
+  /// The result has no valid UUID version; use it to store ULIDs in UUID
+  /// columns, not to pass them off as UUIDs.
+  pub fn to_uuid(&self) -> Uuid {
+    Uuid::from_u128(self.0)
+  }
+
+  /// Builds a ULID from the bits of a `Uuid`
+  ///
+  /// Converting a UUID v7 keeps its timestamp readable.
+  pub fn from_uuid(uuid: &Uuid) -> Self {
+    Self(uuid.as_u128())
+  }
+}
+
+impl fmt::Display for Ulid {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut text = [0u8; ULID_LEN];
+    for (i, c) in text.iter_mut().enumerate() {
+      let shift = 5 * (ULID_LEN - 1 - i);
+      *c = ALPHABET[(self.0 >> shift) as usize & 0x1f];
+    }
+    f.write_str(std::str::from_utf8(&text).expect("alphabet is ASCII"))
+  }
+}
+
+impl FromStr for Ulid {
+  type Err = UlidError;
+
+  /// Parses the text form, ignoring case and reading I and L as 1 and O as 0
+  fn from_str(s: &str) -> Result<Self, UlidError> {
+    if s.len() != ULID_LEN {
+      return Err(UlidError::InvalidLength);
+    }
+    let mut value: u128 = 0;
+    for (i, c) in s.bytes().enumerate() {
+      let digit = decode(c).ok_or(UlidError::InvalidChar)?;
+      // 26 characters hold 130 bits, the first one may only use 3.
+      if i == 0 && digit > 7 {
+        return Err(UlidError::Overflow);
+      }
+      value = value << 5 | u128::from(digit);
+    }
+    Ok(Self(value))
+  }
+}
+
+fn decode(c: u8) -> Option<u8> {
+  match c.to_ascii_uppercase() {
+    b'O' => Some(0),
+    b'I' | b'L' => Some(1),
+    b'U' => None,
+    c => ALPHABET.iter().position(|&a| a == c).map(|i| i as u8),
+  }
+}
+
+impl From<Ulid> for Uuid {
+  fn from(ulid: Ulid) -> Self {
+    ulid.to_uuid()
+  }
+}
+
+impl From<Uuid> for Ulid {
+  fn from(uuid: Uuid) -> Self {
+    Ulid::from_uuid(&uuid)
+  }
+}
+
+fn now_ms() -> u64 {
+  Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// Generates strictly increasing ULIDs, safe to share between threads
+///
+/// Within one millisecond, each ULID is the previous one plus one instead of
+/// fresh random bits. If the clock goes backwards, the generator keeps
+/// counting from the last ULID so the order still holds.
+///
+/// # Examples
+///
+/// ```rust
+/// use helpers::uuid::ulid::Generator;
+///
+/// let generator = Generator::new();
+/// let first = generator.generate().unwrap();
+/// let second = generator.generate().unwrap();
+/// assert!(first < second);
+/// ```
+#[derive(Debug, Default)]
+pub struct Generator {
+  previous: Mutex<Option<Ulid>>,
+}
+
+impl Generator {
+  /// Creates a generator
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Generates the next ULID
+  ///
+  /// # Errors
+  ///
+  /// `UlidError::Overflow` if 2^80 ULIDs were already generated in this
+  /// millisecond, which in practice only happens when the first random part
+  /// was drawn very close to its maximum
+  pub fn generate(&self) -> Result<Ulid, UlidError> {
+    self.generate_at(now_ms(), rand::random())
+  }
+
+  fn generate_at(&self, timestamp_ms: u64, random: u128) -> Result<Ulid, UlidError> {
+    let mut previous = self.previous.lock().unwrap_or_else(|e| e.into_inner());
+    let next = match *previous {
+      Some(last) if last.timestamp_ms() >= timestamp_ms & TIMESTAMP_MASK => {
+        if last.random() == RANDOM_MASK {
+          return Err(UlidError::Overflow);
+        }
+        Ulid(last.0 + 1)
+      }
+      _ => Ulid::from_parts(timestamp_ms, random),
+    };
+    *previous = Some(next);
+    Ok(next)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::thread;
+
+  use uuid::Uuid;
+
+  use crate::uuid::ulid::{Generator, Ulid, UlidError};
+
+  #[test]
+  fn test_encoding() {
+    let ulid: Ulid = "01ARZ3NDEKTSV4RRFFQ69G5FAV".parse().unwrap();
+    assert_eq!(ulid.timestamp_ms(), 1_469_922_850_259);
+    assert_eq!(ulid.to_string(), "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+    assert_eq!("01arz3ndektsv4rrffq69g5fav".parse(), Ok(ulid));
+    assert_eq!(
+      "0LARZ3NDEKTSV4RRFFQ69G5FAV".parse::<Ulid>(),
+      "01ARZ3NDEKTSV4RRFFQ69G5FAV".parse()
+    );
+    assert_eq!(Ulid::default().to_string(), "00000000000000000000000000");
+    assert_eq!(
+      Ulid::from_u128(u128::MAX).to_string(),
+      "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+    );
+    assert_eq!(
+      "7ZZZZZZZZZZZZZZZZZZZZZZZZZ".parse(),
+      Ok(Ulid::from_u128(u128::MAX))
+    );
+    assert_eq!(
+      "8ZZZZZZZZZZZZZZZZZZZZZZZZZ".parse::<Ulid>(),
+      Err(UlidError::Overflow)
+    );
+    assert_eq!("01ARZ3".parse::<Ulid>(), Err(UlidError::InvalidLength));
+    assert_eq!(
+      "01ARZ3NDEKTSV4RRFFQ69G5FAU".parse::<Ulid>(),
+      Err(UlidError::InvalidChar)
+    );
+  }
+
+  #[test]
+  fn test_parts() {
+    let ulid = Ulid::from_parts(1_700_000_000_123, u128::MAX);
+    assert_eq!(ulid.timestamp_ms(), 1_700_000_000_123);
+    assert_eq!(ulid.random(), (1 << 80) - 1);
+    assert_eq!(ulid.datetime().timestamp_millis(), 1_700_000_000_123);
+    assert_eq!(Ulid::from_bytes(ulid.to_bytes()), ulid);
+
+    let uuid: Uuid = ulid.into();
+    assert_eq!(Ulid::from(uuid), ulid);
+    let v7 = Uuid::now_v7();
+    let (seconds, _) = v7.get_timestamp().unwrap().to_unix();
+    assert_eq!(Ulid::from_uuid(&v7).timestamp_ms() / 1_000, seconds);
+
+    let now = Ulid::new();
+    assert!((chrono::Utc::now() - now.datetime()).num_seconds() < 5);
+    assert_ne!(now.random(), Ulid::new().random());
+  }
+
+  #[test]
+  fn test_generator() {
+    let generator = Generator::new();
+    let a = generator.generate_at(1_000, 5).unwrap();
+    let b = generator.generate_at(1_000, 99).unwrap();
+    assert_eq!(b.to_u128(), a.to_u128() + 1);
+    // A clock going backwards keeps counting
+    let c = generator.generate_at(999, 0).unwrap();
+    assert_eq!(c.to_u128(), b.to_u128() + 1);
+    let d = generator.generate_at(1_001, 7).unwrap();
+    assert_eq!(d, Ulid::from_parts(1_001, 7));
+
+    let full = Generator::new();
+    full.generate_at(5, u128::MAX).unwrap();
+    assert_eq!(full.generate_at(5, 0), Err(UlidError::Overflow));
+    assert!(full.generate_at(6, 0).is_ok());
+
+    let shared = Arc::new(Generator::new());
+    let handles: Vec<_> = (0..4)
+      .map(|_| {
+        let generator = Arc::clone(&shared);
+        thread::spawn(move || {
+          (0..1_000)
+            .map(|_| generator.generate().unwrap())
+            .collect::<Vec<_>>()
+        })
+      })
+      .collect();
+    let mut all = Vec::new();
+    for handle in handles {
+      let ids = handle.join().unwrap();
+      assert!(ids.windows(2).all(|w| w[0] < w[1]));
+      all.extend(ids);
+    }
+    all.sort();
+    all.dedup();
+    assert_eq!(all.len(), 4_000);
+  }
+}